@@ -0,0 +1,43 @@
+#version 140
+
+in vec2 v_tex_coords;
+out vec4 color;
+
+uniform sampler2D tex;
+uniform vec2 resolution;
+uniform float strength;
+
+const float threshold = 0.7;
+const int radius = 4;
+
+void main()
+{
+  vec3 source = texture(tex, v_tex_coords).rgb;
+  vec2 texel = 1.0 / resolution;
+
+  // gather the bright parts of the surrounding pixels
+  vec3 glow = vec3(0, 0, 0);
+  float weight_total = 0.0;
+
+  for(int x = -radius; x <= radius; x++)
+  {
+    for(int y = -radius; y <= radius; y++)
+    {
+      vec2 offset = vec2(x, y) * texel * 2.0;
+      float weight = 1.0 - length(vec2(x, y)) / (float(radius) * 1.5);
+
+      if(weight <= 0.0)
+      {
+        continue;
+      }
+
+      vec3 sample_color = texture(tex, v_tex_coords + offset).rgb;
+      float brightness = max(sample_color.r, max(sample_color.g, sample_color.b));
+
+      glow += sample_color * max(brightness - threshold, 0.0) * weight;
+      weight_total += weight;
+    }
+  }
+
+  color = vec4(source + glow / weight_total * strength * 4.0, 1.0);
+}
@@ -0,0 +1,38 @@
+#version 140
+
+in vec2 v_tex_coords;
+out vec4 color;
+
+uniform sampler2D tex;
+uniform vec2 resolution;
+uniform float strength;
+uniform int time;
+
+const float curvature = 0.08;
+
+void main()
+{
+  // bulge the image like a curved tube
+  vec2 centered = v_tex_coords * 2.0 - 1.0;
+  centered *= 1.0 + curvature * strength * dot(centered.yx, centered.yx);
+  vec2 uv = centered * 0.5 + 0.5;
+
+  if(uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0)
+  {
+    color = vec4(0, 0, 0, 1);
+    return;
+  }
+
+  // slight color fringing towards the edges
+  vec2 fringe = (uv - 0.5) * strength / resolution * 2.0;
+  vec3 source = vec3(
+    texture(tex, uv + fringe).r,
+    texture(tex, uv).g,
+    texture(tex, uv - fringe).b
+  );
+
+  float scanline = 0.5 + 0.5 * sin(uv.y * resolution.y * 3.14159 + float(time) * 0.002);
+  source *= 1.0 - (0.25 * strength * scanline);
+
+  color = vec4(source, 1.0);
+}
@@ -0,0 +1,27 @@
+#version 140
+
+in vec2 v_tex_coords;
+out vec4 color;
+
+uniform sampler2D tex;
+uniform float strength;
+
+// warm highlights, cool shadows
+const vec3 shadow_tint = vec3(0.9, 0.95, 1.1);
+const vec3 highlight_tint = vec3(1.1, 1.0, 0.9);
+
+const float contrast = 1.15;
+const float saturation = 1.2;
+
+void main()
+{
+  vec3 source = texture(tex, v_tex_coords).rgb;
+
+  float luminance = dot(source, vec3(0.299, 0.587, 0.114));
+
+  vec3 graded = mix(vec3(luminance), source, saturation);
+  graded = (graded - 0.5) * contrast + 0.5;
+  graded *= mix(shadow_tint, highlight_tint, luminance);
+
+  color = vec4(mix(source, clamp(graded, 0.0, 1.0), strength), 1.0);
+}
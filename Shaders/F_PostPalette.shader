@@ -0,0 +1,30 @@
+#version 140
+
+in vec2 v_tex_coords;
+out vec4 color;
+
+uniform sampler2D tex;
+
+// number of levels each color channel is reduced to
+uniform float strength;
+
+// ordered dither to hide banding between levels
+const mat4 bayer = mat4(
+   0.0,  8.0,  2.0, 10.0,
+  12.0,  4.0, 14.0,  6.0,
+   3.0, 11.0,  1.0,  9.0,
+  15.0,  7.0, 13.0,  5.0
+) / 16.0;
+
+void main()
+{
+  vec3 source = texture(tex, v_tex_coords).rgb;
+
+  ivec2 cell = ivec2(mod(gl_FragCoord.xy, 4.0));
+  float dither = bayer[cell.x][cell.y] - 0.5;
+
+  float levels = max(strength, 2.0) - 1.0;
+  vec3 quantized = floor(source * levels + 0.5 + dither) / levels;
+
+  color = vec4(clamp(quantized, 0.0, 1.0), 1.0);
+}
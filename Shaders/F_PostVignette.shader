@@ -0,0 +1,19 @@
+#version 140
+
+in vec2 v_tex_coords;
+out vec4 color;
+
+uniform sampler2D tex;
+uniform vec2 resolution;
+uniform float strength;
+
+void main()
+{
+  vec3 source = texture(tex, v_tex_coords).rgb;
+
+  // keep the vignette round on wide windows
+  vec2 offset = (v_tex_coords - 0.5) * vec2(resolution.x / resolution.y, 1.0);
+  float falloff = smoothstep(0.4, 1.0, length(offset));
+
+  color = vec4(source * (1.0 - falloff * strength), 1.0);
+}
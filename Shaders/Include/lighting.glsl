@@ -0,0 +1,93 @@
+// Scene lighting for sprites, included by sprite fragment shaders built with LIT.
+// Expects tex, v_normal, v_uv_sign and v_world_position to be declared first.
+
+// lighting information, one column per light
+uniform sampler2D lights;
+uniform int light_count;
+uniform vec3 ambient_light;
+
+// tangent space normals in the same cells as tex
+uniform sampler2D normal_map;
+uniform bool has_normal_map;
+
+// lights sit above the sprite plane by this fraction of their radius
+const float light_height = 0.25;
+
+vec4 ApplyLighting(vec4 color, vec2 samplePoint)
+{
+  vec2 pixel_size = 1.0 / vec2(textureSize(tex, 0));
+  vec3 normal = v_normal;
+
+  if(has_normal_map)
+  {
+    normal = texture(normal_map, samplePoint).rgb * 2.0 - 1.0;
+
+    // mirrored sprites read the sheet backwards, so their normals point the other way
+    normal.xy *= v_uv_sign;
+    normal = normalize(normal);
+  }
+
+  vec3 light_total = ambient_light;
+
+  // direction towards the light that contributes the most, used for rim lighting
+  vec2 rim_direction = vec2(0, 0);
+  float rim_strength = 0;
+  vec3 rim_color = vec3(0, 0, 0);
+
+  for(int i = 0; i < light_count; i++)
+  {
+    vec4 position_radius = texelFetch(lights, ivec2(i, 0), 0);
+    vec4 color_intensity = texelFetch(lights, ivec2(i, 1), 0);
+    vec4 spot = texelFetch(lights, ivec2(i, 2), 0);
+
+    vec2 to_light = position_radius.xy - v_world_position.xy;
+    float light_distance = length(to_light);
+
+    float attenuation = pow(clamp(1.0 - light_distance / position_radius.w, 0.0, 1.0), spot.w);
+
+    // spot lights fade out towards the edge of their cone
+    if(spot.z > -1.0 && light_distance > 0.0)
+    {
+      float cone = dot(-to_light / light_distance, spot.xy);
+      attenuation *= smoothstep(spot.z, mix(spot.z, 1.0, 0.1), cone);
+    }
+
+    // flat sprites are lit evenly, normal mapped ones by the angle to the light
+    vec3 light_direction = normalize(vec3(to_light, light_height * position_radius.w));
+    float diffuse = has_normal_map ? max(dot(normal, light_direction), 0.0) : 1.0;
+
+    float strength = attenuation * color_intensity.w * diffuse;
+    light_total += color_intensity.rgb * strength;
+
+    if(strength > rim_strength && light_distance > 0.0)
+    {
+      rim_strength = strength;
+      rim_direction = to_light / light_distance;
+      rim_color = color_intensity.rgb;
+    }
+  }
+
+  color = vec4(color.rgb * light_total, color.a);
+
+  // rim lighting
+  // light direction is in screen space, sample the sheet in mirrored space
+  if(rim_strength > 0.0)
+  {
+    vec2 light_offset = pixel_size * v_uv_sign * rim_direction;
+    vec4 value = texture(tex, samplePoint + (light_offset));
+    vec4 value2 = texture(tex, samplePoint + (light_offset * 2));
+
+    vec4 rim = vec4(rim_color * rim_strength, 0);
+
+    if(value.a < 0.01)
+    {
+      color = color + (rim * 0.5);
+    }
+    else if(value2.a < 0.01)
+    {
+      color = color + (rim * 0.5 / 1.2);
+    }
+  }
+
+  return max(color, vec4(0,0,0,0));
+}
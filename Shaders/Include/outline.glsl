@@ -0,0 +1,28 @@
+// One pixel border around the opaque pixels of a sprite, included by sprite shaders built with OUTLINE.
+// The sprite needs a transparent pixel of room around it inside its cell.
+
+// material parameter, transparent unless the material sets it
+uniform vec4 outline_color;
+
+// Returns the outline color for a transparent pixel next to an opaque one, otherwise the pixel itself.
+vec4 ApplyOutline(sampler2D sheet, vec4 color, vec2 samplePoint, float transparent)
+{
+  if(color.a >= transparent)
+  {
+    return color;
+  }
+
+  vec2 pixel_size = 1.0 / vec2(textureSize(sheet, 0));
+
+  if(
+      texture(sheet, samplePoint + (vec2(1,0) * pixel_size)).a > transparent ||
+      texture(sheet, samplePoint + (vec2(-1,0) * pixel_size)).a > transparent ||
+      texture(sheet, samplePoint + (vec2(0,1) * pixel_size)).a > transparent ||
+      texture(sheet, samplePoint + (vec2(0,-1) * pixel_size)).a > transparent
+    )
+  {
+    return outline_color;
+  }
+
+  return color;
+}
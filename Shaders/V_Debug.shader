@@ -0,0 +1,13 @@
+#version 140
+
+// Debug lines arrive already projected to the scene target.
+in vec2 position;
+in vec4 color;
+
+out vec4 v_color;
+
+void main()
+{
+    v_color = color;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
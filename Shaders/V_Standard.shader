@@ -1,22 +1,33 @@
-
-#version 140
-
-in vec3 position;
-in vec3 normal;
-in vec2 tex_coords;
-out vec2 v_tex_coords;
-out vec3 v_normal;
-
-uniform mat4 perspective;
-uniform mat4 view;
-uniform mat4 model;
-
-void main() 
-{
-    v_tex_coords = tex_coords;
-    mat4 modelview = view * model;
-    v_normal = vec3(0,0,1);//transpose(inverse(mat3(modelview))) * vec3(0, 0, 1);//normal;
-    gl_Position = perspective * modelview * vec4(position, 1.0);
-
-    //gl_Position = matrix * vec4(position, 0.0, 1.0);
-}
+
+#version 140
+
+in vec3 position;
+in vec2 tex_coords;
+in vec4 tint;
+in float lit;
+in vec2 uv_sign;
+
+out vec2 v_tex_coords;
+out vec3 v_normal;
+out vec4 v_tint;
+out float v_lit;
+out vec2 v_uv_sign;
+out vec3 v_world_position;
+
+uniform mat4 perspective;
+uniform mat4 view;
+uniform mat4 model;
+
+void main() 
+{
+    v_tex_coords = tex_coords;
+    v_tint = tint;
+    v_lit = lit;
+    v_uv_sign = uv_sign;
+    v_world_position = position;
+    mat4 modelview = view * model;
+    v_normal = vec3(0,0,1);//transpose(inverse(mat3(modelview))) * vec3(0, 0, 1);//normal;
+    gl_Position = perspective * modelview * vec4(position, 1.0);
+
+    //gl_Position = matrix * vec4(position, 0.0, 1.0);
+}
@@ -1,12 +1,17 @@
+use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
 use std::sync::Arc;
 
-use glium::{Display, Frame};
+use glium::Display;
 use glium::glutin::surface::WindowSurface;
 use uuid::Uuid;
 use winit::keyboard::KeyCode::*;
 use crate::Engine::Collision::collider::{ECollisionTag, ECollisionType};
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Components::animator::Animator;
 use crate::Engine::Components::Collider::Collider;
+use crate::Engine::Components::particles::ParticleEmitter;
 
 use crate::Engine::Components::RenderComponents::{Renderer2D, Sprite};
 use crate::Engine::Frame::GameFrame;
@@ -14,18 +19,10 @@ use crate::Engine::GameAPI::GameAPI;
 use crate::Engine::GameEntity::TEntity;
 use crate::Engine::GameEntity::EntityHeader;
 use crate::Engine::Math::Float3;
-
-#[derive(Copy, Clone, PartialEq, Eq)]
-enum EPlayerState
-{
-    idle,
-    run,
-    trot,
-    jump,
-    fall,
-    punch1,
-    punch2,
-}
+use crate::Engine::Rendering::{ESortingLayer, Renderer};
+use crate::Engine::Rendering::debug_draw::{EDebugSpace, BLUE, RED, WHITE};
+use crate::Engine::Rendering::material::EMaterialParameter;
+use crate::Engine::ui::widget::EWidget;
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum EDirection
@@ -35,35 +32,45 @@ enum EDirection
 }
 
 
-const RUN_SPEED: f32 = 0.01;
-const LIGHT_SPEED: f32 = 0.015;
-const MEDIUM_SPEED: f32 = 0.015;
-
-// Sprite Asset References
-const IDLE_LEFT: &str  = "Assets/boxer_idle_left.png";
-const IDLE_RIGHT: &str = "Assets/boxer_idle_right.png";
-    
-const RUN_LEFT: &str   = "Assets/boxer_run_left.png";
-const RUN_RIGHT: &str  = "Assets/boxer_run_right.png";
-
-const JUMP_LEFT: &str   = "Assets/boxer_jump_left.png";
-const JUMP_RIGHT: &str  = "Assets/boxer_jump_right.png";
-
-const FALL_LEFT: &str   = "Assets/boxer_fall_left.png";
-const FALL_RIGHT: &str  = "Assets/boxer_fall_right.png";
-
-const TROT_LEFT: &str   = "Assets/boxer_trot_left.png";
-const TROT_RIGHT: &str  = "Assets/boxer_trot_right.png";
-
-const PUNCH1_LEFT: &str   = "Assets/boxer_1_left.png";
-const PUNCH1_RIGHT: &str  = "Assets/boxer_1_right.png";
-
-const PUNCH2_LEFT: &str   = "Assets/boxer_2_right.png";
-const PUNCH2_RIGHT: &str  = "Assets/boxer_2_right.png";
-
+// Animator definition containing all boxer clips and states.
+const ANIMATOR_PATH: &str = "Animations/boxer.anim";
 
 const WATER_BALL_SPRITE: &str = "Assets/waterball.png";
-const WATER_SHOOT_SFX: &str = "Assets/Shoot.ogg";
+
+// Flashes white when a punch connects, fading over HIT_FLASH_SECONDS.
+const HIT_FLASH_MATERIAL: &str = "Materials/hit_flash.mat";
+const HIT_FLASH_PARAMETER: &str = "flash_color";
+const HIT_FLASH_SECONDS: f32 = 0.15;
+
+// Animation Events
+const HIT_ACTIVE_EVENT: &str = "hit_active";
+const FOOTSTEP_EVENT: &str = "footstep";
+
+// Fist position while punching, for the unflipped sprite, and the area it hits.
+const FIST_OFFSET: (f32, f32) = (14.0, 2.0);
+const FIST_REACH: (f32, f32) = (12.0, 12.0);
+
+// Camera feedback when a punch connects.
+const HIT_TRAUMA: f32 = 0.35;
+const HIT_KICK: f32 = 3.0;
+const HIT_ZOOM_PUNCH: f32 = 0.05;
+const HIT_ZOOM_PUNCH_SECONDS: f32 = 0.15;
+
+// Particles
+const HIT_SPARKS_PATH: &str = "Particles/hit_sparks.particles";
+const HIT_SPARK_COUNT: u32 = 14;
+const DUST_PATH: &str = "Particles/dust.particles";
+const LANDING_DUST_COUNT: u32 = 10;
+const FOOTSTEP_DUST_COUNT: u32 = 2;
+
+// Menu and HUD, see UI/menu.ui.
+const FIGHT_BUTTON: &str = "Fight";
+const HEALTH_BAR: &str = "Health";
+const GAME_OVER_ACTION: &str = "Hide HUD; Show MenuShade; Focus Fight";
+
+// Falling below this height costs health and returns the boxer to where it started.
+const FALL_LIMIT_Y: f32 = -400.0;
+const FALL_DAMAGE: f32 = 0.25;
 
 const GRAVITY : f32 = 0.5;
 const JUMP_STRENGTH: f32 = 35.0;
@@ -79,21 +86,51 @@ pub struct Boxer
     pub _velocity: Float3,
     pub _lastInputVector: Float3,
 
-    _spriteTable: [Arc<Sprite>; 14],
+    _animator: Animator,
 
-    _state : EPlayerState,
     _direction :  EDirection,
     _display: Display<WindowSurface>,
 
     _waterSprite: Arc<Sprite>,
-    _isAttacking: bool,
+
+    _hitSparks: ParticleEmitter,
+    _dust: ParticleEmitter,
+    _wasGrounded: bool,
+
+    /// Between 0 and 1, shown by the HUD health bar.
+    _health: f32,
+    _spawnPosition: Float3,
+
+    /// Footstep events raised since the last update, counted by an animation subscriber.
+    _footsteps: Rc<Cell<u32>>,
 }
 
 impl Boxer
 {
-    pub fn Create(movementSpeed: f32, display: &Display<WindowSurface>, position: Float3) -> Self
+    pub fn Create(
+        movementSpeed: f32,
+        display: &Display<WindowSurface>,
+        position: Float3,
+        assets: &mut AssetManager) -> Self
     {
-        let default = Sprite::new(IDLE_LEFT, display, 8, (3,3), RUN_SPEED);
+        let animator = Animator::FromFile(ANIMATOR_PATH, display, assets);
+        let default = animator.EntryClip().Sprite.clone();
+
+        let mut renderer = Renderer2D::New(default, true);
+        renderer.SetSortingLayer(ESortingLayer::Characters, 0);
+        renderer.SetMaterial(&assets.Material(HIT_FLASH_MATERIAL, display));
+
+        // Sparks come off the fist, dust from the feet.
+        let mut hitSparks = ParticleEmitter::FromFile(HIT_SPARKS_PATH, display, assets);
+        hitSparks.Offset = Float3::new(FIST_OFFSET.0, FIST_OFFSET.1, 0.0);
+
+        let mut dust = ParticleEmitter::FromFile(DUST_PATH, display, assets);
+        dust.Offset = Float3::new(0.0, -14.0, 0.0);
+
+        let footsteps = Rc::new(Cell::new(0));
+        let counter = footsteps.clone();
+        renderer.AnimationMut().Subscribe(FOOTSTEP_EVENT, Box::new(move |_| counter.set(counter.get() + 1)));
+
         Self
         {
             Header: EntityHeader::Create("Player", position),
@@ -101,83 +138,127 @@ impl Boxer
             _velocity: Float3::zero(),
             _lastInputVector: Float3::zero(),
 
-            _spriteTable:
-                [
-                    Sprite::new(IDLE_LEFT, display, 8, (3,3), RUN_SPEED),
-                    Sprite::new(IDLE_RIGHT, display, 8, (3,3), RUN_SPEED),
-                    
-                    Sprite::new(RUN_LEFT, display, 8, (3,3), RUN_SPEED),
-                    Sprite::new(RUN_RIGHT, display, 8, (3,3), RUN_SPEED),
-                    
-                    Sprite::new(TROT_LEFT, display, 5, (3,2), RUN_SPEED),
-                    Sprite::new(TROT_RIGHT, display, 5, (3,2), RUN_SPEED),
-               
-                    Sprite::new(JUMP_LEFT, display, 2, (2,1), RUN_SPEED),
-                    Sprite::new(JUMP_RIGHT, display, 2, (2,1), RUN_SPEED),
-                    
-                    Sprite::new(FALL_LEFT, display, 2, (2,1), RUN_SPEED),
-                    Sprite::new(FALL_RIGHT, display, 2, (2,1), RUN_SPEED),
-               
-                    Sprite::new(PUNCH1_LEFT, display, 3, (2,2), LIGHT_SPEED),
-                    Sprite::new(PUNCH1_RIGHT, display, 3, (2,2), LIGHT_SPEED),
-                    
-                    Sprite::new(PUNCH2_LEFT, display, 4, (2,2), MEDIUM_SPEED),
-                    Sprite::new(PUNCH2_RIGHT, display, 4, (2,2), MEDIUM_SPEED),
-                ],
-
-            _renderer2d: Renderer2D::New(display, default, true),
+            _animator: animator,
+
+            _renderer2d: renderer,
             _collider: Collider::Create(
-                display.clone(),
                 position,
                 Float3::new(32.0,32.0,1.0),
                 ECollisionType::Solid,
                 ECollisionTag::None
-
             ),
-            _state: EPlayerState::idle,
             _direction: EDirection::Left,
             _display: display.clone(),
-            _waterSprite: Sprite::new_simple(WATER_BALL_SPRITE, display),
-            _isAttacking: false,
+            _waterSprite: assets.SimpleSprite(WATER_BALL_SPRITE, display),
+            _hitSparks: hitSparks,
+            _dust: dust,
+            _wasGrounded: true,
+            _health: 1.0,
+            _spawnPosition: position,
+            _footsteps: footsteps,
         }
     }
 
-    fn animation_update(&mut self, state: EPlayerState, direction: EDirection)
+    /// Pushes the current movement state to the animator.
+    /// hasInput - False while the UI has focus, attacks are ignored.
+    fn AnimationUpdate(&mut self, isGrounded: bool, hasInput: bool, frame: &GameFrame)
     {
-        let renderer = &mut self._renderer2d;
+        let animator = &mut self._animator;
 
-        if self._isAttacking 
+        animator.SetBool("grounded", isGrounded);
+        animator.SetFloat("speed", self._velocity.x().abs());
+        animator.SetFloat("velocity_y", self._velocity.y());
+
+        if hasInput && frame.Input.IsKeyPressed(KeyU)
         {
-            if renderer.IsComplete()
-            {
-                self._isAttacking = false;
-            }
-            else
-            {
-                return;
-            }
+            animator.SetTrigger("punch1");
         }
 
-        if state == EPlayerState::punch1 || state == EPlayerState::punch2
+        if hasInput && frame.Input.IsKeyPressed(KeyI)
         {
+            animator.SetTrigger("punch2");
+        }
+
+        animator.Update(&mut self._renderer2d);
+
+        // Sheets face right, keep facing locked while an attack plays out.
+        if !animator.IsLocked(&self._renderer2d)
+        {
+            let flipX = self._direction == EDirection::Left;
+
+            self._renderer2d.SetFlip(flipX, false);
+            self._collider.SetFlip(flipX, false);
+        }
+    }
+
+    /// Checks the fist's reach on the active punch frame, feedback only plays when something is hit.
+    unsafe fn Punch(&mut self, api: *mut GameAPI)
+    {
+        let facing = if self._direction == EDirection::Left {-1.0} else {1.0};
+        let reach = self.Header.WorldPosition + Float3::new(facing * FIST_OFFSET.0, FIST_OFFSET.1, 0.0);
+        let reachSize = Float3::new(FIST_REACH.0, FIST_REACH.1, 1.0);
+
+        let hasConnected = (*api).Collision.IsThereSolidCollisionInArea(&self.Header.ID(), reach, reachSize);
 
-            let index = Self::IndexFromState(state, direction);
-            renderer.SetSprite1Loop(self._spriteTable[index].clone());
-            
-            self._isAttacking = true;
+        (*api).Debug.Rect(EDebugSpace::World, reach, reachSize, if hasConnected {RED} else {WHITE}, 0.25);
 
+        if !hasConnected
+        {
             return;
         }
 
-        // render all other simple states
-        let index = (state as usize  * 2) + direction as usize;
-       
-        renderer.set_new_sprite(self._spriteTable[index].clone());
+        let camera = (*api).SceneManager.Entities.MainCamera();
+
+        camera.AddTrauma(HIT_TRAUMA);
+        camera.Kick(Float3::new(facing * HIT_KICK, 0.0, 0.0));
+        camera.ZoomPunch(HIT_ZOOM_PUNCH, HIT_ZOOM_PUNCH_SECONDS);
+
+        self._hitSparks.FlipX = self._direction == EDirection::Left;
+        self._hitSparks.Burst(HIT_SPARK_COUNT);
+
+        self._renderer2d.MaterialMut().Animate(
+            HIT_FLASH_PARAMETER,
+            EMaterialParameter::Color([1.0, 1.0, 1.0, 1.0]),
+            EMaterialParameter::Color([1.0, 1.0, 1.0, 0.0]),
+            HIT_FLASH_SECONDS);
+    }
+
+    /// Fight from the menu starts over at full health, falling out of the level costs health
+    /// and losing all of it goes back to the menu.
+    unsafe fn UpdateHealth(&mut self, api: *mut GameAPI)
+    {
+        let ui = &mut (*api).SceneManager.Entities.UI;
+
+        if ui.WasActivated(FIGHT_BUTTON)
+        {
+            self._health = 1.0;
+            self.Respawn();
+        }
+
+        if self.Header.WorldPosition.y() < FALL_LIMIT_Y
+        {
+            self._health = (self._health - FALL_DAMAGE).max(0.0);
+            self.Respawn();
+
+            if self._health <= 0.0
+            {
+                ui.RunAction(GAME_OVER_ACTION);
+            }
+        }
+
+        if let Some(id) = ui.Find(HEALTH_BAR)
+        {
+            if let EWidget::HealthBar(x) = &mut ui.WidgetMut(id).Kind
+            {
+                x.SetValue(self._health);
+            }
+        }
     }
 
-    fn IndexFromState(state: EPlayerState, direction: EDirection) -> usize
+    fn Respawn(&mut self)
     {
-        (state as usize  * 2) + direction as usize
+        self.Header.WorldPosition = self._spawnPosition;
+        self._velocity = Float3::zero();
     }
 
     fn CheckState(&mut self)
@@ -213,39 +294,27 @@ impl TEntity for Boxer
     unsafe fn Start(&mut self, api: *mut GameAPI)
     {
         self._collider.Start(&self.Header, api);
-        self._renderer2d.Start(&self.Header, api);
     }
 
     unsafe fn Update(&mut self, frame: &GameFrame, api: *mut GameAPI)
     {
-        ///*
-        let cam_pos = (*api).SceneManager.Entities.Camera.Header.WorldPosition.clone();
-        let mut player_pos = self.Header.WorldPosition.clone();
-
-        println!("Player Pos: {}", player_pos);
-        println!("Cam Pos: {}", cam_pos);
-
-         (*api).SceneManager.Entities.Camera.Header.WorldPosition =
-             Float3::Lerp(
-                 cam_pos,
-                 player_pos
-                     .OverrideY(self.Header.WorldPosition.y() + 0.0)
-                     .AddZ(256.0 * 4.0),
-                 0.5f32 * frame.DeltaTime_Seconds
-             );
-         //*/
-
         self._collider.Update(&self.Header, frame, api);
+        self._renderer2d.Update(frame);
+
+        if self._renderer2d.Animation().HasEventFired(HIT_ACTIVE_EVENT)
+        {
+            self.Punch(api);
+        }
+
 
         let id = &self.Header.ID();
         let entity= &mut self.Header;
 
-        let oldDirection = self._direction;
-        let oldState = self._state;
+        // A focused widget owns the keyboard, so the boxer stands still behind an open menu.
+        let hasInput = (*api).SceneManager.Entities.UI.Focused().is_none();
 
-
-        let leftVector : f32 = if frame.Input.IsKeyDown(KeyA) {-1.0} else {0.0};
-        let rightVector: f32 = if frame.Input.IsKeyDown(KeyD) {1.0} else {0.0};
+        let leftVector : f32 = if hasInput && frame.Input.IsKeyDown(KeyA) {-1.0} else {0.0};
+        let rightVector: f32 = if hasInput && frame.Input.IsKeyDown(KeyD) {1.0} else {0.0};
         let upVector : f32 = if frame.Input.IsKeyDown(KeyW) {1.0} else {0.0};
         let downVector : f32 = if frame.Input.IsKeyDown(KeyS) {-1.0} else {0.0};
 
@@ -255,7 +324,7 @@ impl TEntity for Boxer
 
         let damping;
 
-        if inputVector.magnitude() > 0.001 && !self._isAttacking
+        if inputVector.magnitude() > 0.001 && !self._animator.IsLocked(&self._renderer2d)
         {
             damping = 1.0;
             self._lastInputVector = inputVector;
@@ -303,7 +372,7 @@ impl TEntity for Boxer
         }
 
         // Jump logic
-        if frame.Input.IsKeyPressed(KeyW) && isGrounded
+        if hasInput && frame.Input.IsKeyPressed(KeyW) && isGrounded
         {
             self._velocity = self._velocity.OverrideY(JUMP_STRENGTH);
         }
@@ -342,8 +411,6 @@ impl TEntity for Boxer
                     i = i - (sign * step);
                     x_comp.OverrideX(i);
 
-                    println!("X OVERRIDE{}", i);
-
                     if !(*api).Collision.IsThereSolidCollisionAt(
                         &entity.ID(),
                         entity.WorldPosition + Float3::new(i, 0.0, 0.0))
@@ -373,7 +440,6 @@ impl TEntity for Boxer
                 {
                     i = i - (sign * step);
                     y_comp.OverrideY(i);
-                    println!("Y OVERRIDE{}", i);
 
                     if !(*api).Collision.IsThereSolidCollisionAt(
                         &entity.ID(),
@@ -387,53 +453,35 @@ impl TEntity for Boxer
         }
 
 
-        if(isGrounded)
-        {
-            if(self._velocity.x().abs() > 15.0)
-            {
-                self._state = EPlayerState::run;
-            }
-            else if(self._velocity.x().abs() > 3.0)
-            {
-                self._state = EPlayerState::trot;
-            }
-            else
-            {
-                self._state = EPlayerState::idle;
-            }
-        }
-        else
-        {
-            if(self._velocity.y() > 0.0)
-            {
-                self._state = EPlayerState::jump;
-            }
-            else
-            {
-                self._state = EPlayerState::fall;
-            }
-        }
+        self.UpdateHealth(api);
 
-        if frame.Input.IsKeyPressed(KeyU) && !self._isAttacking
+        if isGrounded && !self._wasGrounded
         {
-            self._state = EPlayerState::punch1;
+            self._dust.Burst(LANDING_DUST_COUNT);
         }
 
-        if frame.Input.IsKeyPressed(KeyI) && !self._isAttacking
-        {
-            self._state = EPlayerState::punch2;
-        }
+        self._wasGrounded = isGrounded;
+
+        let footsteps = self._footsteps.replace(0);
 
-        
-        if
-            self._isAttacking ||
-            (oldState != self._state || oldDirection != self._direction)
+        if isGrounded && footsteps > 0
         {
-            self.animation_update(self._state, self._direction);
+            self._dust.Burst(FOOTSTEP_DUST_COUNT * footsteps);
         }
 
-        println!("{}", self._velocity);
-        println!("BOXER CLOSE");
+        self.AnimationUpdate(isGrounded, hasInput, frame);
+
+        self._hitSparks.Update(&self.Header, frame);
+        self._dust.Update(&self.Header, frame);
+
+        let debug = &mut (*api).Debug;
+        debug.Arrow(EDebugSpace::World, self.Header.WorldPosition, self.Header.WorldPosition + self._velocity, BLUE, 0.0);
+        debug.Text(
+            EDebugSpace::World,
+            self.Header.WorldPosition + Float3::new(-16.0, 28.0, 0.0),
+            if isGrounded {"GROUNDED"} else {"AIRBORNE"},
+            WHITE,
+            0.0);
 
     }
 
@@ -442,9 +490,11 @@ impl TEntity for Boxer
         self._collider.OnDestroy(&self.Header, api);
     }
 
-    fn Render(&mut self, frame: &GameFrame, target: &mut Frame)
+    fn Render(&mut self, _frame: &GameFrame, renderer: &mut Renderer)
     {
-        self._renderer2d.Render(&self.Header, frame, target);
+        self._renderer2d.Render(&self.Header, renderer);
+        self._hitSparks.Render(&self.Header, renderer);
+        self._dust.Render(&self.Header, renderer);
     }
 }
 
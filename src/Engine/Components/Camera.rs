@@ -1,191 +1,530 @@
-use std::fmt::{Debug, Formatter};
-use std::sync::{Arc, Mutex};
-
-use cgmath::{Matrix4, ortho, perspective};
-use glium::Frame;
-use uuid::Uuid;
-use winit::event::MouseButton;
-use crate::Engine::DEBUG_MODE;
-
-use crate::Engine::Frame::GameFrame;
-use crate::Engine::GameAPI::GameAPI;
-use crate::Engine::GameEntity::{EntityHeader, TEntity};
-use crate::Engine::Math::Float3;
-
-pub enum EProjectionType
-{
-    Perspective,
-    Orthographic
-}
-
-pub struct Camera
-{
-    pub Header: EntityHeader,
-    pub FocalDirection: Float3,
-    pub UpDirection : Float3,
-    pub FieldOfView : f32,
-    pub Projection : EProjectionType,
-
-    _editorController : EditorCameraController
-
-}
-
-impl Camera
-{
-    pub fn New(fov: f32, position: Float3) -> Self
-    {
-        Self
-        {
-            Header: EntityHeader::Create("Camera", position),
-            FocalDirection: Float3::new(0.0, 0.0, 1.0),
-            UpDirection: Float3::up(),
-            FieldOfView: fov,
-            Projection: EProjectionType::Perspective,
-            _editorController : EditorCameraController::New()
-        }
-    }
-
-    pub fn PerspectiveMatrix(&self) -> Matrix4<f32>
-    {
-        match self.Projection
-        {
-            EProjectionType::Orthographic =>
-                {
-                    let bound_width = 0.2;
-                    let bound_height = 0.2;
-
-                    ortho(-bound_width, bound_width, -bound_height, bound_height, -100.0, 100.0)
-
-                }
-            EProjectionType::Perspective =>
-                {
-                    perspective(cgmath::Deg(self.FieldOfView), 1.0, 0.1, 100.0)
-                }
-        }
-    }
-
-    pub fn ViewMatrix(&self) -> Matrix4<f32>
-    {
-        let viewMod = 1.0 / 32.0;
-        let viewScale = 1.0;
-        let x = self.Header.WorldPosition.x() * viewMod;
-        let y = self.Header.WorldPosition.y() * viewMod;
-        let z = self.Header.WorldPosition.z() * viewMod;
-
-        return Matrix4::from([
-            [1.0 * viewScale, 0.0, 0.0, 0.0],
-            [0.0, 1.0 * viewScale, 0.0, 0.0],
-            [0.0, 0.0, 1.0 * viewScale, 0.0],
-            [-x * viewMod, -y * viewMod, -z * viewMod, 1.0 * viewScale]
-        ]);
-    }
-
-    pub fn ScaleMatrix(&self) -> Matrix4<f32>
-    {
-        let scale = self.Header.WorldPosition.z() / 100.0;
-        return Matrix4::from_scale(scale);
-        return Matrix4::from([
-            [scale, 1.0, 1.0, 1.0],
-            [1.0, scale, 1.0, 1.0],
-            [1.0, 1.0, 1.0, 1.0],
-            [1.0, 1.0, 1.0, 1.0]
-        ]);
-    }
-
-
-}
-
-impl Debug for Camera {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
-    {
-        write!(f, "Camera")
-    }
-}
-
-impl TEntity for Camera
-{
-    fn HasStartBeenCalled(&self) -> bool
-    {
-        self.Header.HasStartBeenCalled()
-    }
-
-    fn ID(&self) -> Uuid
-    {
-        self.Header.ID()
-    }
-
-    unsafe fn Start(&mut self, api: *mut GameAPI)
-    {
-
-    }
-
-    unsafe fn Update(&mut self, frame: &GameFrame, api: *mut GameAPI)
-    {
-        self._editorController.Update(&mut self.Header, frame, api );
-    }
-
-    unsafe fn OnDestroy(&mut self, api: *mut GameAPI)
-    {
-    }
-
-    fn Render(&mut self, frame: &GameFrame, target: &mut Frame)
-    {
-
-    }
-}
-
-
-/// Camera controller module that is used in edit mode.
-pub struct EditorCameraController
-{
-    _initialWorldPosition : Float3,
-    _initialMousePosition: Float3,
-    _delta: Float3
-}
-
-impl EditorCameraController
-{
-    pub fn New() -> Self
-    {
-        Self
-        {
-            _initialMousePosition: Float3::zero(),
-            _initialWorldPosition: Float3::zero(),
-            _delta: Float3::zero()
-        }
-    }
-
-
-    unsafe fn Update(&mut self, entity: &mut EntityHeader, frame: &GameFrame, api: *mut GameAPI)
-    {
-        if !DEBUG_MODE
-        {
-            return;
-        }
-
-        //TODO If in editor mode, allow controls
-
-        let mousePosition = frame.Input.MousePosition();
-        let vectorPosition = Float3::new(-mousePosition.0 as f32 / 300.0, mousePosition.1 as f32 / 300.0, 0.0);
-
-        if frame.Input.IsMousePressed(MouseButton::Middle)
-        {
-            // cache initial position
-            self._initialMousePosition = vectorPosition;
-            self._initialWorldPosition = entity.WorldPosition;
-        }
-
-        if frame.Input.IsMouseButtonDown(MouseButton::Middle)
-        {
-            // calculate delta position
-            self._delta = vectorPosition - self._initialMousePosition;
-            entity.WorldPosition = self._initialWorldPosition + self._delta;
-        }
-
-        let scroll = frame.Input.MouseWheelLineDelta();
-        let zAdd = -scroll.1 / 10.0;
-        entity.WorldPosition = entity.WorldPosition + Float3::new(0.0, 0.0, zAdd as f32);
-    }
-}
-
-
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+use cgmath::{Matrix4, SquareMatrix, Vector4, ortho, perspective};
+use glium::Rect;
+use uuid::Uuid;
+use winit::event::MouseButton;
+use crate::Engine::PIXELS_PER_UNIT;
+
+use crate::Engine::Components::camera_follow::CameraFollow;
+use crate::Engine::Components::camera_shake::CameraShake;
+use crate::Engine::Rendering::{ESortingLayer, RenderView, Renderer};
+use crate::Engine::Rendering::debug_draw::{DebugDraw, EDebugSpace, YELLOW};
+use crate::Engine::Rendering::virtual_screen::VirtualScreen;
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::GameEntity::{EntityHeader, TEntity};
+use crate::Engine::Math::Float3;
+
+pub enum EProjectionType
+{
+    Perspective,
+    Orthographic
+}
+
+impl EProjectionType
+{
+    /// Parses a projection from a scene file, Orthographic or Perspective.
+    pub fn FromString(value: &str) -> Self
+    {
+        match value.trim().to_lowercase().as_str()
+        {
+            "orthographic" => EProjectionType::Orthographic,
+            "perspective" => EProjectionType::Perspective,
+            x => panic!("Unknown projection {}", x)
+        }
+    }
+}
+
+/// Distance between the near and far planes either side of the camera.
+const DEPTH_RANGE: f32 = 10000.0;
+
+/// Part of the screen a camera draws into, as fractions of the screen size.
+/// The origin is the bottom left corner.
+#[derive(Copy, Clone, Debug)]
+pub struct ViewportRect
+{
+    pub X: f32,
+    pub Y: f32,
+    pub Width: f32,
+    pub Height: f32
+}
+
+impl ViewportRect
+{
+    pub fn New(x: f32, y: f32, width: f32, height: f32) -> Self
+    {
+        Self { X: x, Y: y, Width: width, Height: height }
+    }
+
+    /// The whole screen.
+    pub fn Full() -> Self
+    {
+        Self::New(0.0, 0.0, 1.0, 1.0)
+    }
+
+    /// Pixel area within a screen of the given size, at least one pixel across.
+    pub fn Pixels(&self, screen: (u32, u32)) -> Rect
+    {
+        let left = (self.X * screen.0 as f32).round() as u32;
+        let bottom = (self.Y * screen.1 as f32).round() as u32;
+        let right = ((self.X + self.Width) * screen.0 as f32).round() as u32;
+        let top = ((self.Y + self.Height) * screen.1 as f32).round() as u32;
+
+        Rect
+        {
+            left,
+            bottom,
+            width: right.saturating_sub(left).max(1),
+            height: top.saturating_sub(bottom).max(1)
+        }
+    }
+}
+
+pub struct Camera
+{
+    pub Header: EntityHeader,
+    pub FocalDirection: Float3,
+    pub UpDirection : Float3,
+    pub FieldOfView : f32,
+    pub Projection : EProjectionType,
+
+    /// Screen pixels per world pixel. 2 shows half as much of the world at twice the size.
+    pub Zoom: f32,
+
+    /// Rounds the view position to whole pixels so the scene does not shimmer while moving.
+    pub PixelSnap: bool,
+
+    /// Moves the camera after a target entity when one is set.
+    pub Follow: CameraFollow,
+
+    /// Impact offsets applied on top of the followed position.
+    pub Shake: CameraShake,
+
+    /// Part of the screen this camera draws into.
+    pub Viewport: ViewportRect,
+
+    /// Sorting layers this camera draws, see ESortingLayer::Mask.
+    pub LayerMask: u32,
+
+    /// Color the viewport is cleared to before drawing. None draws over cameras before it.
+    pub ClearColor: Option<(f32, f32, f32, f32)>,
+
+    /// Cameras are drawn from lowest to highest priority, so higher ones end up on top.
+    pub Priority: i32,
+
+    /// Ignores its position and shows screen pixels with the origin at the bottom left
+    /// of its viewport. Used to draw the UI layer.
+    pub IsScreenSpace: bool,
+
+    _editorController : EditorCameraController,
+
+    /// Scene state for this frame's Update, see PrepareUpdate.
+    _isHovered: bool,
+    _targetPosition: Option<Float3>
+
+}
+
+impl Camera
+{
+    pub fn New(fov: f32, position: Float3) -> Self
+    {
+        Self
+        {
+            Header: EntityHeader::Create("Camera", position),
+            FocalDirection: Float3::new(0.0, 0.0, 1.0),
+            UpDirection: Float3::up(),
+            FieldOfView: fov,
+            Projection: EProjectionType::Orthographic,
+            Zoom: 1.0,
+            PixelSnap: true,
+            Follow: CameraFollow::Create(),
+            Shake: CameraShake::Create(),
+            Viewport: ViewportRect::Full(),
+            LayerMask: ESortingLayer::ALL,
+            ClearColor: Some((0.1, 0.0, 0.2, 1.0)),
+            Priority: 0,
+            IsScreenSpace: false,
+            _editorController : EditorCameraController::New(),
+            _isHovered: false,
+            _targetPosition: None
+        }
+    }
+
+    /// Camera that draws only the UI layer in screen pixels on top of every other camera.
+    pub fn NewScreenSpace(name: &str, priority: i32) -> Self
+    {
+        let mut camera = Self::New(30.0, Float3::zero());
+        camera.Header.Name = String::from(name);
+        camera.LayerMask = ESortingLayer::UI.Mask();
+        camera.ClearColor = None;
+        camera.Priority = priority;
+        camera.PixelSnap = true;
+        camera.IsScreenSpace = true;
+        camera
+    }
+
+    /// Half of the visible height in world units for a viewport of the given pixel size.
+    /// Ignores zoom punches, so following and bounds stay steady.
+    pub fn OrthographicSize(&self, viewport: (u32, u32)) -> f32
+    {
+        viewport.1 as f32 / (2.0 * PIXELS_PER_UNIT * self.Zoom)
+    }
+
+    /// Zoom including any active zoom punch.
+    pub fn EffectiveZoom(&self) -> f32
+    {
+        self.Zoom * self.Shake.ZoomMultiplier()
+    }
+
+    /// Distance a perspective camera sits from the z = 0 plane so that
+    /// the plane is framed the same as in orthographic mode.
+    pub fn PerspectiveDistance(&self, viewport: (u32, u32)) -> f32
+    {
+        self.ViewHalfHeight(viewport) / (self.FieldOfView.to_radians() * 0.5).tan()
+    }
+
+    /// Adds shake trauma between 0 and 1, i.e. 0.3 for a light hit.
+    pub fn AddTrauma(&mut self, amount: f32)
+    {
+        self.Shake.AddTrauma(amount);
+    }
+
+    /// Pushes the view in a direction, it springs back on its own.
+    pub fn Kick(&mut self, impulse: Float3)
+    {
+        self.Shake.Kick(impulse);
+    }
+
+    /// Zooms in by amount, i.e. 0.1 for 10%, and eases back out over seconds.
+    pub fn ZoomPunch(&mut self, amount: f32, seconds: f32)
+    {
+        self.Shake.ZoomPunch(amount, seconds);
+    }
+
+    /// Size in pixels of this camera's viewport on a screen of the given size.
+    pub fn ViewportSize(&self, screen: (u32, u32)) -> (u32, u32)
+    {
+        let rect = self.Viewport.Pixels(screen);
+        (rect.width, rect.height)
+    }
+
+    /// Scene state read by the next Update, gathered by Entities before the cameras update.
+    /// isHovered - Whether this is the topmost world camera under the cursor, false over the UI.
+    /// targetPosition - Position of the follow target this frame, None if it is gone.
+    pub fn PrepareUpdate(&mut self, isHovered: bool, targetPosition: Option<Float3>)
+    {
+        self._isHovered = isHovered;
+        self._targetPosition = targetPosition;
+    }
+
+    /// Everything the renderer needs to draw this camera on a screen of the given size.
+    pub fn RenderView(&self, screen: (u32, u32)) -> RenderView
+    {
+        let viewport = self.ViewportSize(screen);
+
+        RenderView
+        {
+            View: self.ViewMatrix(viewport),
+            Projection: self.PerspectiveMatrix(viewport),
+            Viewport: self.Viewport.Pixels(screen),
+            LayerMask: self.LayerMask,
+            Position: self.ViewPosition(),
+            ClearColor: self.ClearColor
+        }
+    }
+
+    /// True if a window position, i.e. the mouse, is inside this camera's viewport.
+    pub fn ContainsScreenPoint(&self, windowPosition: (f64, f64), screen: &VirtualScreen) -> bool
+    {
+        let rect = self.Viewport.Pixels((screen.Width, screen.Height));
+        let (x, y) = screen.WindowToVirtual(windowPosition);
+
+        x >= rect.left as f32 && x < (rect.left + rect.width) as f32 &&
+            y >= rect.bottom as f32 && y < (rect.bottom + rect.height) as f32
+    }
+
+    /// World position under a window position, i.e. the mouse.
+    ///
+    /// Goes through the letterbox, this camera's viewport and the same
+    /// projection used for drawing, so it holds for any zoom, shake or projection.
+    /// The result lies on the z = 0 plane sprites are drawn on.
+    pub fn ScreenToWorld(&self, windowPosition: (f64, f64), screen: &VirtualScreen) -> Float3
+    {
+        let screenSize = (screen.Width, screen.Height);
+        let rect = self.Viewport.Pixels(screenSize);
+        let viewport = (rect.width, rect.height);
+        let (x, y) = screen.WindowToVirtual(windowPosition);
+
+        let ndcX = (x - rect.left as f32) / rect.width as f32 * 2.0 - 1.0;
+        let ndcY = (y - rect.bottom as f32) / rect.height as f32 * 2.0 - 1.0;
+
+        let inverse = match (self.PerspectiveMatrix(viewport) * self.ViewMatrix(viewport)).invert()
+        {
+            Some(x) => x,
+            None => return self.Header.WorldPosition
+        };
+
+        let near = inverse * Vector4::new(ndcX, ndcY, -1.0, 1.0);
+        let far = inverse * Vector4::new(ndcX, ndcY, 1.0, 1.0);
+        let near = near.truncate() / near.w;
+        let far = far.truncate() / far.w;
+
+        // Follow the ray through the pixel until it reaches z = 0.
+        let direction = far - near;
+        let t = if direction.z.abs() > f32::EPSILON { -near.z / direction.z } else { 0.0 };
+        let point = near + direction * t;
+
+        Float3::new(point.x, point.y, point.z)
+    }
+
+    /// Window position, origin top left, a world position is drawn at.
+    pub fn WorldToScreen(&self, worldPosition: Float3, screen: &VirtualScreen) -> (f64, f64)
+    {
+        let rect = self.Viewport.Pixels((screen.Width, screen.Height));
+        let viewport = (rect.width, rect.height);
+
+        let clip = self.PerspectiveMatrix(viewport) * self.ViewMatrix(viewport) *
+            Vector4::new(worldPosition.x(), worldPosition.y(), worldPosition.z(), 1.0);
+
+        let w = if clip.w.abs() > f32::EPSILON { clip.w } else { 1.0 };
+        let ndcX = clip.x / w;
+        let ndcY = clip.y / w;
+
+        screen.VirtualToWindow(
+            (
+                rect.left as f32 + (ndcX + 1.0) * 0.5 * rect.width as f32,
+                rect.bottom as f32 + (ndcY + 1.0) * 0.5 * rect.height as f32
+            ))
+    }
+
+    /// Marks the world pixel under the cursor and its coordinates, mapped back onto the screen.
+    fn DrawHoveredPixel(&self, mouseWorld: Float3, screen: &VirtualScreen, debug: &mut DebugDraw)
+    {
+        let pixel = Float3::new(
+            (mouseWorld.x() * PIXELS_PER_UNIT).floor() / PIXELS_PER_UNIT,
+            (mouseWorld.y() * PIXELS_PER_UNIT).floor() / PIXELS_PER_UNIT,
+            0.0);
+
+        let center = pixel + Float3::new(0.5 / PIXELS_PER_UNIT, 0.5 / PIXELS_PER_UNIT, 0.0);
+        let (x, y) = screen.WindowToVirtual(self.WorldToScreen(center, screen));
+        let size = self.Zoom;
+
+        debug.Rect(EDebugSpace::Screen, Float3::new(x, y, 0.0), Float3::new(size, size, 0.0), YELLOW, 0.0);
+        debug.Text(
+            EDebugSpace::Screen,
+            Float3::new(x + 4.0, y + 10.0, 0.0),
+            format!("{}, {}", pixel.x(), pixel.y()).as_str(),
+            YELLOW,
+            0.0);
+    }
+
+    /// Half of the visible width and height in world units this frame, including zoom punches.
+    pub fn VisibleHalfSize(&self, viewport: (u32, u32)) -> (f32, f32)
+    {
+        let halfHeight = self.ViewHalfHeight(viewport);
+        (halfHeight * viewport.0 as f32 / viewport.1.max(1) as f32, halfHeight)
+    }
+
+    /// Half of the visible height this frame, including zoom punches.
+    fn ViewHalfHeight(&self, viewport: (u32, u32)) -> f32
+    {
+        viewport.1 as f32 / (2.0 * PIXELS_PER_UNIT * self.EffectiveZoom())
+    }
+
+    /// Projection for a viewport of the given pixel size.
+    /// The aspect ratio comes from the viewport, so world pixels stay square.
+    pub fn PerspectiveMatrix(&self, viewport: (u32, u32)) -> Matrix4<f32>
+    {
+        let aspect = viewport.0 as f32 / viewport.1.max(1) as f32;
+
+        match self.Projection
+        {
+            EProjectionType::Orthographic =>
+                {
+                    let bound_height = self.ViewHalfHeight(viewport);
+                    let bound_width = bound_height * aspect;
+
+                    ortho(-bound_width, bound_width, -bound_height, bound_height, -DEPTH_RANGE, DEPTH_RANGE)
+                }
+            EProjectionType::Perspective =>
+                {
+                    perspective(cgmath::Deg(self.FieldOfView), aspect, 1.0, DEPTH_RANGE)
+                }
+        }
+    }
+
+    /// Moves the world so the camera position is at the center of the view,
+    /// then rolls it by the current shake.
+    pub fn ViewMatrix(&self, viewport: (u32, u32)) -> Matrix4<f32>
+    {
+        if self.IsScreenSpace
+        {
+            let halfHeight = self.OrthographicSize(viewport);
+            let halfWidth = halfHeight * viewport.0 as f32 / viewport.1.max(1) as f32;
+
+            return Matrix4::from_translation(cgmath::Vector3::new(-halfWidth, -halfHeight, 0.0));
+        }
+
+        let position = self.ViewPosition();
+
+        let z = match self.Projection
+        {
+            EProjectionType::Orthographic => position.z(),
+            EProjectionType::Perspective => position.z() + self.PerspectiveDistance(viewport)
+        };
+
+        Matrix4::from_angle_z(cgmath::Rad(-self.Shake.Rotation())) *
+            Matrix4::from_translation(cgmath::Vector3::new(-position.x(), -position.y(), -z))
+    }
+
+    /// Position the view is centered on, including shake,
+    /// snapped to whole pixels if enabled.
+    pub fn ViewPosition(&self) -> Float3
+    {
+        let position = self.Header.WorldPosition + self.Shake.Offset();
+
+        if !self.PixelSnap
+        {
+            return position;
+        }
+
+        Float3::new(
+            (position.x() * PIXELS_PER_UNIT).round() / PIXELS_PER_UNIT,
+            (position.y() * PIXELS_PER_UNIT).round() / PIXELS_PER_UNIT,
+            position.z())
+    }
+}
+
+impl Debug for Camera {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "Camera")
+    }
+}
+
+impl TEntity for Camera
+{
+    fn HasStartBeenCalled(&self) -> bool
+    {
+        self.Header.HasStartBeenCalled()
+    }
+
+    fn ID(&self) -> Uuid
+    {
+        self.Header.ID()
+    }
+
+    unsafe fn Start(&mut self, api: *mut GameAPI)
+    {
+
+    }
+
+    unsafe fn Update(&mut self, frame: &GameFrame, api: *mut GameAPI)
+    {
+        if self.IsScreenSpace
+        {
+            return;
+        }
+
+        let screen = &(*api).Screen;
+        let mousePosition = frame.Input.MousePosition();
+        let mouseWorld = self.ScreenToWorld(mousePosition, screen);
+
+        // Only the topmost camera under the cursor responds, i.e. not the main view below a minimap,
+        // and none while the cursor is over a widget so clicks on the UI don't drag the view.
+        let isHovered = self._isHovered;
+
+        let isDevMode = (*api).DevOverlay.IsEnabled();
+        self._editorController.Update(&mut self.Header, &mut self.Zoom, mouseWorld, isHovered, isDevMode, frame);
+
+        if isDevMode && isHovered
+        {
+            self.DrawHoveredPixel(mouseWorld, screen, &mut (*api).Debug);
+        }
+
+        let viewport = self.ViewportSize(((*api).Screen.Width, (*api).Screen.Height));
+        let halfHeight = self.OrthographicSize(viewport);
+        let halfWidth = halfHeight * viewport.0 as f32 / viewport.1 as f32;
+
+        // A drag in developer mode moves the camera by hand, following would pull it back.
+        if !self._editorController.IsDragging()
+        {
+            self.Follow.Update(&mut self.Header, self._targetPosition, (halfWidth, halfHeight), frame);
+        }
+
+        // Shake only offsets the view, the followed position is left untouched.
+        self.Shake.Update(frame);
+    }
+
+    unsafe fn OnDestroy(&mut self, api: *mut GameAPI)
+    {
+    }
+
+    fn Render(&mut self, frame: &GameFrame, _renderer: &mut Renderer)
+    {
+
+    }
+}
+
+
+/// Camera controller module that is used in edit mode.
+pub struct EditorCameraController
+{
+    /// World position grabbed when the drag started, kept under the cursor while dragging.
+    _grabPoint: Option<Float3>
+}
+
+impl EditorCameraController
+{
+    pub fn New() -> Self
+    {
+        Self
+        {
+            _grabPoint: None
+        }
+    }
+
+    /// True while the middle mouse button is dragging the view.
+    pub fn IsDragging(&self) -> bool
+    {
+        self._grabPoint.is_some()
+    }
+
+    /// mouseWorld - World position under the cursor for this camera.
+    /// isHovered - Whether the cursor is over this camera's viewport.
+    /// isEnabled - Controls only respond in developer mode.
+    fn Update(&mut self, entity: &mut EntityHeader, zoom: &mut f32, mouseWorld: Float3, isHovered: bool, isEnabled: bool, frame: &GameFrame)
+    {
+        if !isEnabled
+        {
+            self._grabPoint = None;
+            return;
+        }
+
+        if isHovered && frame.Input.IsMousePressed(MouseButton::Middle)
+        {
+            self._grabPoint = Some(mouseWorld);
+        }
+
+        if !frame.Input.IsMouseButtonDown(MouseButton::Middle)
+        {
+            self._grabPoint = None;
+        }
+
+        // Dragging moves the world with the cursor.
+        if let Some(grabPoint) = self._grabPoint
+        {
+            let delta = grabPoint - mouseWorld;
+            entity.WorldPosition.AddX(delta.x());
+            entity.WorldPosition.AddY(delta.y());
+        }
+
+        if isHovered
+        {
+            let scroll = frame.Input.MouseWheelLineDelta();
+            *zoom = (*zoom * (1.0 + scroll.1 as f32 / 10.0)).max(0.1);
+        }
+    }
+}
@@ -1,420 +1,435 @@
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
-
-use glium::{Display, Frame, Program, Surface, Texture2d, VertexBuffer};
-use glium::glutin::surface::WindowSurface;
-use glium::index::NoIndices;
-use glium::texture::RawImage2d;
-use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter};
-
-use crate::Engine::Components::RenderUtilities::{ImageBufferFromPath, Indicies, PlaneVertexBuffer, Vertex};
-use crate::Engine::Frame::GameFrame;
-use crate::Engine::GameAPI::GameAPI;
-use crate::Engine::GameEntity::EntityHeader;
-use crate::Engine::Math::Float3;
-use crate::Engine::Shader::{DEFAULT_FRAGMENT, DEFAULT_VERTEX};
-
-pub struct LightSource
-{
-    pub Color : Float3,
-    pub Intensity: f32
-}
-
-impl LightSource
-{
-    pub fn start(&mut self, api: Arc<Mutex<GameAPI>>)
-    {
-
-    }
-
-    pub fn update(&mut self, frame: &GameFrame, api: Arc<Mutex<GameAPI>>)
-    {
-
-    }
-}
-
-
-
-/// Draws a 2D sprite to screen.
-pub struct Renderer2D
-{
-    pub VertexBuffer: VertexBuffer<Vertex>,
-    pub Indices: NoIndices,
-    pub Program: Program,
-    pub Display: Display<WindowSurface>,
-    pub Sprite: Arc<Sprite>,
-
-    _vertexShader: Option<String>,
-    _fragmentShader: Option<String>,
-    _playTime: Instant,
-   
-    _currentIndex: i32,
-    _loops: bool,
-    _completed: bool,
-
-    _isLit: bool,
-}
-
-impl Renderer2D
-{
-    /// Creates a new 2D Rendering component
-    /// 
-    /// Display - Display Reference
-    /// Sprite - Sprite that should be rendered
-    pub fn New(
-        display : &Display<WindowSurface>, 
-        initialSprite: Arc<Sprite>,
-        isLit: bool
-        ) -> Self
-    {
-        let vertexBuffer = PlaneVertexBuffer(&display);
-
-        Self
-        {
-            Display: display.clone(),
-            Sprite: initialSprite,
-            VertexBuffer: vertexBuffer,
-            Indices: Indicies(),
-            Program: Program::from_source(display, DEFAULT_VERTEX_SHADER, DEFAULT_FRAGMENT_SHADER, None).unwrap(),
-            _fragmentShader: None,
-            _vertexShader: None,
-
-            _currentIndex: 0,
-            _loops: true,
-            _completed: false,
-            _playTime: Instant::now(),
-            _isLit: isLit,
-        }
-    }
-
-    pub fn SetSprite1Loop(&mut self, newSprite: Arc<Sprite>)
-    {
-        self._playTime = Instant::now();
-        self._currentIndex = 0;
-        self.Sprite = newSprite;
-        self._loops = false;
-        self._completed = false;
-    }
-
-    pub fn set_new_sprite(&mut self, newSprite: Arc<Sprite>)
-    {
-        self._playTime = Instant::now();
-        self._currentIndex = 0;
-        self.Sprite = newSprite;
-        self._loops = true;
-        self._completed = false;
-    }
-
-    // properties
-
-    /// Current index of sprite
-    pub fn CurrentIndex(&self) -> i32
-    {
-        self._currentIndex
-    }
-
-
-    /// Returns true if this is a non looped animation and it has finished playing.
-    pub fn IsComplete(&self) -> bool
-    {
-        self._completed
-    }
-
-    pub fn ChangeLightState(&mut self, isLit: bool)
-    {
-        self._isLit = isLit;
-    }
-
-    pub fn Render(&mut self, entity: &EntityHeader, frame: &GameFrame, target: &mut Frame)
-    {
-        
-        // calculate sprite index
-        let elapsedTime = self._playTime.elapsed().as_millis() as f32;
-
-        if self._loops || !self._completed
-        {
-            self._currentIndex 
-                = 
-                (
-                    (elapsedTime * self.Sprite.AnimationSpeed as f32) 
-                    % 
-                    self.Sprite.FrameCount as f32
-                ) 
-                as i32;
-
-            if !self._loops && 
-                (self._currentIndex == (self.Sprite.FrameCount - 1) as i32)
-            {
-                self._completed = true;
-            }
-        }
-
-
-
-        let dim = self.Display.get_framebuffer_dimensions();
-
-        let behavior = glium::uniforms::SamplerBehavior
-        {
-            minify_filter: MinifySamplerFilter::Nearest,
-            magnify_filter: MagnifySamplerFilter::Nearest,
-            ..Default::default()
-        };
-
-        let x = entity.WorldPosition.x();
-        let y = entity.WorldPosition.y();
-        let z = entity.WorldPosition.z();
-
-
-        let display_width = dim.0 as f32;
-        let display_height = dim.1 as f32;
-
-        let image_dimension_x = self.Sprite.Texture.dimensions().0 as f32;
-        let image_dimension_y = self.Sprite.Texture.dimensions().1 as f32;
-        let cell_count_x = self.Sprite.CellCounts.0 as f32;
-        let cell_count_y = self.Sprite.CellCounts.1 as f32;
-
-        let scale = 
-        if(entity.WorldPosition.z() < 0.0)
-        {
-            Float3::scale_value(entity.scale, -entity.WorldPosition.z())
-        }
-        else
-        {
-            entity.scale
-        };
-
-        let rawTransform =
-        [
-            [
-                scale.x() * image_dimension_x / (1.0 * display_width * cell_count_x), 
-                0.0, 
-                0.0, 
-                0.0],
-            [
-                0.0, 
-                scale.y() * image_dimension_y / (1.0 * display_height * cell_count_y), 
-                0.0, 
-                0.0],
-            [
-                0.0, 
-                0.0, 
-                1.0, 
-                0.0],
-            [
-                x / display_width, 
-                y / display_height, 
-                z, 
-                1.0],
-        ];
-
-        let view_mat : [[f32;4];4] = frame.CameraView.into();
-        let perspective_mat : [[f32;4];4] = frame.CameraPerspective.into();
-
-
-        let uniforms = uniform!
-        {
-            view: view_mat,
-            model: rawTransform,
-            perspective: perspective_mat,
-
-            tex: glium::uniforms::Sampler(&self.Sprite.Texture, behavior),
-            is_lit: self._isLit,
-            current_index: self._currentIndex,
-            pixel_dimension_x: image_dimension_x,
-            pixel_dimension_y: image_dimension_y,
-            time: frame.TimeSinceGameStart.num_milliseconds() as i32,
-            cell_x_count: self.Sprite.CellCounts.0 as f32,
-            cell_y_count: self.Sprite.CellCounts.1 as f32,
-            frame_count: self.Sprite.FrameCount as f32,
-            speed: self.Sprite.AnimationSpeed
-        };
-
-        let params = glium::DrawParameters{
-            depth: glium::Depth
-                {
-                    test: glium::draw_parameters::DepthTest::IfLess,
-                    write: true,
-                    .. Default::default()
-                },
-
-                .. Default::default()
-        };
-
-        target.draw(&self.VertexBuffer, &self.Indices, &self.Program, &uniforms,
-                    &params//Default::default()
-
-        ).unwrap();
-    }
-
-    pub unsafe fn Start(&mut self, entity: &EntityHeader, api: *mut GameAPI)
-    {
-        let vertexBuffer = PlaneVertexBuffer(&self.Display);
-
-
-        let loadedFragmentShader: String = 
-        match &self._fragmentShader
-        {
-            Some(x) => (*api).Shader.GetShader(x.as_str()),
-            None => (*api).Shader.GetShader(DEFAULT_FRAGMENT)
-        };
-
-        let loadedVertexShader: String = 
-        match &self._vertexShader
-        {
-            Some(x) => (*api).Shader.GetShader(x.as_str()),
-            None => (*api).Shader.GetShader(DEFAULT_VERTEX)
-        };
-        
-
-        let result =
-            Program::from_source(&self.Display, loadedVertexShader.as_str(), loadedFragmentShader.as_str(), None);
-
-        match result
-        {
-            Ok(_) => {}
-            Err(x) => {panic!("Render Program Error: {}", x.to_string())}
-        }
-
-        let program = result.unwrap();
-
-        self.VertexBuffer = vertexBuffer;
-        self.Indices = Indicies();
-        self.Program = program;
-        self._fragmentShader = Some(loadedFragmentShader);
-        self._vertexShader = Some(loadedVertexShader);
-    }
-}
-
-pub struct Sprite
-{
-    /// Based texture contains sprite sheet
-    pub Texture: Texture2d,
-
-    /// Total Number of sprites in sheet
-    pub FrameCount: u16,
-
-    /// numbers cells horizontally and vertically in sprite sheet
-    pub CellCounts: (u16, u16),
-
-    /// Speed the animation plays at
-    pub AnimationSpeed: f32
-}
-
-impl Sprite
-{
-    /// Creates a simple, un animated sprite
-    pub fn new_simple(spritePath: &str, display: &Display<WindowSurface>) -> Arc<Self>
-    {
-        let imageBuffer = ImageBufferFromPath(spritePath);
-        let image_dimensions = imageBuffer.dimensions();
-        let image = RawImage2d::from_raw_rgba_reversed(&imageBuffer.into_raw(), image_dimensions);
-        let texture = Texture2d::new(display, image).unwrap();
-
-        Arc::new(
-            Sprite
-            {
-                Texture: texture,
-                FrameCount: 1,
-                CellCounts: (1,1),
-                AnimationSpeed: 1.0
-            }
-        )
-    }
-
-    /// Creates a new Sprite.
-    ///
-    /// spritePath - Path to Sprite Image (png).
-    /// display - Display reference.
-    /// frameCount - total number of frames.
-    /// cellCounts - number of cell rows and columns.
-    /// animationSpeed - speed animation should play at.
-    pub fn new
-    (spritePath: &str, display: &Display<WindowSurface>, frameCount: u16,
-     cellCounts: (u16, u16), animationSpeed: f32) -> Arc<Sprite>
-    {
-        let imageBuffer = ImageBufferFromPath(spritePath);
-        let image_dimensions = imageBuffer.dimensions();
-        let image = RawImage2d::from_raw_rgba_reversed(&imageBuffer.into_raw(), image_dimensions);
-        let texture = Texture2d::new(display, image).unwrap();
-
-        Arc::new(
-            Sprite
-            {
-                Texture: texture,
-                FrameCount: frameCount,
-                CellCounts: cellCounts,
-                AnimationSpeed: animationSpeed
-            })
-    }
-}
-
-
-
-pub const DEFAULT_FRAGMENT_SHADER: &str = 
-    r#"
-    #version 140
-
-    in vec2 v_tex_coords;
-    out vec4 color;
-
-    uniform int time;
-    uniform float frame_count;
-    uniform float cell_x_count;
-    uniform float cell_y_count;
-    uniform float speed;
-
-    uniform sampler2D tex;
-
-    void main()
-    {
-        int currentIndex = int(mod(time * speed, frame_count));
-
-        vec2 cellSize
-            = vec2(
-                1.0 / cell_x_count,
-                1.0 / cell_y_count
-            );
-
-        vec2 offset = vec2(
-             mod(float(currentIndex), cell_x_count) / cell_x_count,
-              1 - (0.5 * floor(2 * float(currentIndex) * cellSize.x * cellSize.y))
-        );
-
-
-        vec2 cellCoord = vec2(
-            v_tex_coords.x * cellSize.x,
-            -(1 - v_tex_coords.y) * cellSize.y
-        );
-
-        vec2 samplePoint = offset + cellCoord;
-        color = texture(tex, samplePoint);
-
-        if(color.a < .01)
-        {
-            discard;
-        }
-    }
-    "#;
-
-pub const DEFAULT_VERTEX_SHADER: &str = 
-    r#"
-    #version 140
-
-    in vec3 position;
-    in vec3 normal;
-    in vec2 tex_coords;
-    out vec2 v_tex_coords;
-    out vec3 v_normal;
-
-    uniform mat4 perspective;
-    uniform mat4 view;
-    uniform mat4 model;
-
-    void main() {
-        v_tex_coords = tex_coords;
-        mat4 modelview = view * model;
-        v_normal = transpose(inverse(mat3(modelview))) * normal;
-        gl_Position = perspective * modelview * vec4(position, 1.0);
-        //gl_Position = matrix * vec4(position, 0.0, 1.0);
-    }
-    "#;
+use std::sync::Arc;
+
+use glium::{Display, Texture2d};
+use glium::glutin::surface::WindowSurface;
+use glium::texture::RawImage2d;
+
+use crate::Engine::Components::animation::{AnimationClip, AnimationPlayer};
+use crate::Engine::Components::RenderUtilities::{GifSheetFromPath, ImageBufferFromPath};
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameEntity::EntityHeader;
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, LightingData, Renderer, SpriteQuad};
+use crate::Engine::Rendering::material::{EMaterialParameter, Material, TINT_PARAMETER};
+
+/// Emits light onto lit sprites around its entity.
+pub struct LightSource
+{
+    pub Color : Float3,
+    pub Intensity: f32,
+
+    /// Distance in world units at which the light fades out completely.
+    pub Radius: f32,
+
+    /// Exponent applied to the fade, 1 is linear, higher values fall off faster.
+    pub Falloff: f32,
+
+    /// Direction a spot light points in.
+    pub Direction: Float3,
+
+    /// Full angle of the spot light cone in degrees, 360 for a point light.
+    pub ConeAngle: f32
+}
+
+impl LightSource
+{
+    /// Creates a light shining in every direction.
+    pub fn Point(color: Float3, intensity: f32, radius: f32, falloff: f32) -> Self
+    {
+        Self
+        {
+            Color: color,
+            Intensity: intensity,
+            Radius: radius,
+            Falloff: falloff,
+            Direction: Float3::down(),
+            ConeAngle: 360.0
+        }
+    }
+
+    /// Creates a light shining in a cone around direction.
+    pub fn Spot(color: Float3, intensity: f32, radius: f32, falloff: f32, direction: Float3, coneAngle: f32) -> Self
+    {
+        Self
+        {
+            Color: color,
+            Intensity: intensity,
+            Radius: radius,
+            Falloff: falloff,
+            Direction: direction.normalized(),
+            ConeAngle: coneAngle
+        }
+    }
+
+    /// Submits the light at the entity's position for this frame.
+    pub fn Render(&self, entity: &EntityHeader, renderer: &mut Renderer)
+    {
+        renderer.SubmitLight(
+            LightingData
+            {
+                Position: entity.WorldPosition,
+                Color: self.Color,
+                Intensity: self.Intensity,
+                Radius: self.Radius,
+                Falloff: self.Falloff,
+                Direction: self.Direction,
+                ConeAngle: self.ConeAngle
+            });
+    }
+}
+
+
+
+/// Draws a 2D sprite to screen.
+pub struct Renderer2D
+{
+    /// Shader and parameters the sprite is drawn with.
+    /// Shared, i.e. with the AssetManager, until this sprite changes it.
+    _material: Arc<Material>,
+
+    _animation: AnimationPlayer,
+
+    _isLit: bool,
+
+    _layer: ESortingLayer,
+    _orderInLayer: i32,
+
+    _flipX: bool,
+    _flipY: bool,
+
+    /// Named points local to the entity, i.e. where a fist or muzzle is.
+    _attachmentPoints: Vec<(String, Float3)>,
+}
+
+impl Renderer2D
+{
+    /// Creates a new 2D Rendering component
+    /// 
+    /// Sprite - Sprite that should be rendered
+    pub fn New(
+        initialSprite: Arc<Sprite>,
+        isLit: bool
+        ) -> Self
+    {
+        Self
+        {
+            _material: Arc::new(Material::Default()),
+
+            _animation: AnimationPlayer::Create(
+                Arc::new(AnimationClip::FromSprite("default", initialSprite, true))),
+            _isLit: isLit,
+            _layer: ESortingLayer::World,
+            _orderInLayer: 0,
+            _flipX: false,
+            _flipY: false,
+            _attachmentPoints: Vec::new(),
+        }
+    }
+
+    /// Plays the sprite once and holds on the last frame.
+    pub fn SetSprite1Loop(&mut self, newSprite: Arc<Sprite>)
+    {
+        self._animation.Play(Arc::new(AnimationClip::FromSprite("sprite", newSprite, false)));
+    }
+
+    /// Plays the sprite on a loop.
+    pub fn set_new_sprite(&mut self, newSprite: Arc<Sprite>)
+    {
+        self._animation.Play(Arc::new(AnimationClip::FromSprite("sprite", newSprite, true)));
+    }
+
+    /// Plays an animation clip from its first frame.
+    pub fn PlayClip(&mut self, clip: Arc<AnimationClip>)
+    {
+        self._animation.Play(clip);
+    }
+
+    /// Advances the current animation and material animations by the frame's game time.
+    /// Frame events raised by this step can be read from Animation().
+    pub fn Update(&mut self, frame: &GameFrame)
+    {
+        self._animation.Advance(frame.GameDeltaTime_Milliseconds());
+
+        // Only animated materials are written to, the rest stay shared.
+        if self._material.HasAnimations()
+        {
+            Arc::make_mut(&mut self._material).Update(frame);
+        }
+    }
+
+    // properties
+
+    /// Animation player driving this renderer.
+    pub fn Animation(&self) -> &AnimationPlayer
+    {
+        &self._animation
+    }
+
+    /// Mutable animation player, used to subscribe to frame events.
+    pub fn AnimationMut(&mut self) -> &mut AnimationPlayer
+    {
+        &mut self._animation
+    }
+
+    /// Current index of sprite
+    pub fn CurrentIndex(&self) -> i32
+    {
+        self._animation.SheetIndex() as i32
+    }
+
+
+    /// Returns true if this is a non looped animation and it has finished playing.
+    pub fn IsComplete(&self) -> bool
+    {
+        self._animation.IsComplete()
+    }
+
+    pub fn ChangeLightState(&mut self, isLit: bool)
+    {
+        self._isLit = isLit;
+    }
+
+    /// Sets the layer the sprite is drawn in and its order within that layer.
+    /// Higher orders are drawn on top.
+    pub fn SetSortingLayer(&mut self, layer: ESortingLayer, orderInLayer: i32)
+    {
+        self._layer = layer;
+        self._orderInLayer = orderInLayer;
+    }
+
+    /// Replaces the material, shared until MaterialMut copies it for this sprite.
+    pub fn SetMaterial(&mut self, material: &Arc<Material>)
+    {
+        self._material = material.clone();
+    }
+
+    pub fn Material(&self) -> &Material
+    {
+        &self._material
+    }
+
+    /// Material of this sprite only, i.e. to set or animate its parameters.
+    /// A shared material is copied on first use.
+    pub fn MaterialMut(&mut self) -> &mut Material
+    {
+        Arc::make_mut(&mut self._material)
+    }
+
+    /// Mirrors the sprite horizontally and/or vertically.
+    /// Allows a single sheet to be used for both facing directions.
+    pub fn SetFlip(&mut self, flipX: bool, flipY: bool)
+    {
+        self._flipX = flipX;
+        self._flipY = flipY;
+    }
+
+    pub fn FlipX(&self) -> bool
+    {
+        self._flipX
+    }
+
+    pub fn FlipY(&self) -> bool
+    {
+        self._flipY
+    }
+
+    /// Adds or replaces a named point relative to the entity origin.
+    /// The offset is authored for the unflipped sprite.
+    pub fn SetAttachmentPoint(&mut self, name: &str, localOffset: Float3)
+    {
+        match self._attachmentPoints.iter_mut().find(|x| x.0 == name)
+        {
+            Some(x) => x.1 = localOffset,
+            None => self._attachmentPoints.push((String::from(name), localOffset))
+        }
+    }
+
+    /// World position of a named point, mirrored to match the current flip.
+    pub fn AttachmentPoint(&self, entity: &EntityHeader, name: &str) -> Option<Float3>
+    {
+        self._attachmentPoints
+            .iter()
+            .find(|x| x.0 == name)
+            .map(|x|
+                entity.WorldPosition +
+                Float3::scale_vector(x.1, entity.scale).Mirror(self._flipX, self._flipY))
+    }
+
+    /// Submits the current animation frame to the renderer.
+    pub fn Render(&mut self, entity: &EntityHeader, renderer: &mut Renderer)
+    {
+        let sprite = self._animation.Clip().Sprite.clone();
+
+        let cell_count_x = sprite.CellCounts.0 as f32;
+        let cell_count_y = sprite.CellCounts.1 as f32;
+
+        // Size of one cell in world units.
+        let cell_width = sprite.Texture.dimensions().0 as f32 / (cell_count_x * PIXELS_PER_UNIT);
+        let cell_height = sprite.Texture.dimensions().1 as f32 / (cell_count_y * PIXELS_PER_UNIT);
+
+        let scale = entity.scale;
+
+        let half_width = 0.5 * scale.x() * cell_width;
+        let half_height = 0.5 * scale.y() * cell_height;
+        let origin = entity.WorldPosition;
+
+        // Sheets are laid out left to right, top to bottom and uploaded flipped.
+        let index = self.CurrentIndex() as f32;
+        let column = index % cell_count_x;
+        let row = (index / cell_count_x).floor();
+
+        let mut uvMin = [column / cell_count_x, 1.0 - (row + 1.0) / cell_count_y];
+        let mut uvMax = [(column + 1.0) / cell_count_x, 1.0 - row / cell_count_y];
+
+        if self._flipX
+        {
+            (uvMin[0], uvMax[0]) = (uvMax[0], uvMin[0]);
+        }
+
+        if self._flipY
+        {
+            (uvMin[1], uvMax[1]) = (uvMax[1], uvMin[1]);
+        }
+
+        let tint = match self._material.Get(TINT_PARAMETER)
+        {
+            Some(EMaterialParameter::Color(x)) => *x,
+            _ => [1.0, 1.0, 1.0, 1.0]
+        };
+
+        renderer.Submit(
+            SpriteQuad
+            {
+                Texture: sprite.Texture.clone(),
+                NormalMap: sprite.NormalMap.clone(),
+                Shader: String::from(self._material.DrawShader(self._isLit)),
+                Parameters: Some(self._material.Parameters().clone()),
+                Corners:
+                [
+                    origin + Float3::new(-half_width, -half_height, 0.0),
+                    origin + Float3::new( half_width, -half_height, 0.0),
+                    origin + Float3::new( half_width,  half_height, 0.0),
+                    origin + Float3::new(-half_width,  half_height, 0.0),
+                ],
+                UVMin: uvMin,
+                UVMax: uvMax,
+                Tint: tint,
+                IsLit: self._isLit,
+                ScrollFactor: Float3::one(),
+                Layer: self._layer,
+                OrderInLayer: self._orderInLayer
+            });
+    }
+}
+
+pub struct Sprite
+{
+    /// Based texture contains sprite sheet
+    pub Texture: Arc<Texture2d>,
+
+    /// Total Number of sprites in sheet
+    pub FrameCount: u16,
+
+    /// numbers cells horizontally and vertically in sprite sheet
+    pub CellCounts: (u16, u16),
+
+    /// Speed the animation plays at
+    pub AnimationSpeed: f32,
+
+    /// Display time of each frame in milliseconds.
+    /// Empty when every frame plays at AnimationSpeed.
+    pub FrameDurations: Vec<f32>,
+
+    /// Tangent space normals laid out in the same cells as Texture.
+    /// Lit sprites without one are lit as a flat plane.
+    pub NormalMap: Option<Arc<Texture2d>>
+}
+
+impl Sprite
+{
+    /// Creates a simple, un animated sprite
+    pub fn new_simple(spritePath: &str, display: &Display<WindowSurface>) -> Arc<Self>
+    {
+        Self::new(spritePath, display, 1, (1,1), 1.0)
+    }
+
+    /// Creates a new Sprite.
+    /// Prefer AssetManager::Sprite so the texture is shared between users.
+    ///
+    /// spritePath - Path to Sprite Image (png).
+    /// display - Display reference.
+    /// frameCount - total number of frames.
+    /// cellCounts - number of cell rows and columns.
+    /// animationSpeed - speed animation should play at.
+    pub fn new
+    (spritePath: &str, display: &Display<WindowSurface>, frameCount: u16,
+     cellCounts: (u16, u16), animationSpeed: f32) -> Arc<Sprite>
+    {
+        Self::FromTexture(
+            Arc::new(Self::LoadTexture(spritePath, display)),
+            frameCount,
+            cellCounts,
+            animationSpeed)
+    }
+
+    /// Creates a Sprite from an already loaded texture.
+    pub fn FromTexture(texture: Arc<Texture2d>, frameCount: u16,
+                       cellCounts: (u16, u16), animationSpeed: f32) -> Arc<Sprite>
+    {
+        Self::FromTextures(texture, None, frameCount, cellCounts, animationSpeed)
+    }
+
+    /// Creates a Sprite from an already loaded texture and optional normal map.
+    /// The normal map must have the same cell layout as the texture.
+    pub fn FromTextures(texture: Arc<Texture2d>, normalMap: Option<Arc<Texture2d>>,
+                        frameCount: u16, cellCounts: (u16, u16), animationSpeed: f32) -> Arc<Sprite>
+    {
+        Arc::new(
+            Sprite
+            {
+                Texture: texture,
+                FrameCount: frameCount,
+                CellCounts: cellCounts,
+                AnimationSpeed: animationSpeed,
+                FrameDurations: Vec::new(),
+                NormalMap: normalMap
+            })
+    }
+
+    /// Decodes an image and uploads it to the GPU.
+    pub fn LoadTexture(spritePath: &str, display: &Display<WindowSurface>) -> Texture2d
+    {
+        let imageBuffer = ImageBufferFromPath(spritePath);
+        let image_dimensions = imageBuffer.dimensions();
+        let image = RawImage2d::from_raw_rgba_reversed(&imageBuffer.into_raw(), image_dimensions);
+
+        Texture2d::new(display, image).unwrap()
+    }
+
+    /// Creates a Sprite from an animated gif.
+    /// Frames are packed into a sprite sheet and each frame keeps its original delay.
+    ///
+    /// spritePath - Path to gif.
+    /// display - Display reference.
+    pub fn new_gif(spritePath: &str, display: &Display<WindowSurface>) -> Arc<Sprite>
+    {
+        let sheet = GifSheetFromPath(spritePath);
+        let image_dimensions = sheet.Image.dimensions();
+        let image = RawImage2d::from_raw_rgba_reversed(&sheet.Image.into_raw(), image_dimensions);
+        let texture = Texture2d::new(display, image).unwrap();
+
+        let totalDuration: f32 = sheet.FrameDurations.iter().sum();
+
+        Arc::new(
+            Sprite
+            {
+                Texture: Arc::new(texture),
+                FrameCount: sheet.FrameCount,
+                CellCounts: sheet.CellCounts,
+                AnimationSpeed: sheet.FrameCount as f32 / totalDuration,
+                FrameDurations: sheet.FrameDurations,
+                NormalMap: None
+            })
+    }
+}
@@ -1,54 +1,115 @@
-use glium::VertexBuffer;
-use glium::Display;
-use glium::glutin::surface::WindowSurface;
-use glium::index::NoIndices;
-use image::{ImageBuffer, Rgba};
-
-#[derive(Copy, Clone)]
-pub struct Vertex
-{
-    position: [f32; 3],
-    tex_coords: [f32; 2],
-}
-
-pub fn PlaneVertexBuffer(display: &Display<WindowSurface>) -> VertexBuffer<Vertex>
-{
-    implement_vertex!(Vertex, position, tex_coords);
-
-    let shape = vec![
-        Vertex { position: [-0.5, -0.5, 0.0], tex_coords: [0.0, 0.0] },
-        Vertex { position: [ 0.5, -0.5, 0.0], tex_coords: [1.0, 0.0] },
-        Vertex { position: [ 0.5,  0.5, 0.0], tex_coords: [1.0, 1.0] },
-
-        Vertex { position: [ 0.5,  0.5, 0.0], tex_coords: [1.0, 1.0] },
-        Vertex { position: [-0.5,  0.5, 0.0], tex_coords: [0.0, 1.0] },
-        Vertex { position: [-0.5, -0.5, 0.0], tex_coords: [0.0, 0.0] },
-    ];
-
-
-    VertexBuffer::new(display, &shape).unwrap()
-}
-
-pub fn Indicies() -> NoIndices
-{
-    NoIndices(glium::index::PrimitiveType::TrianglesList)
-}
-
-pub fn ImageBufferFromPath(path: &str) -> ImageBuffer<Rgba<u16>, Vec<u16>>
-{
-    // Read bytes from the file at the given path
-    let bytes = match std::fs::read(path)
-    {
-        Ok(content) => content,
-        Err(err) => {
-            panic!("Error reading file from path: {} \n {}", path,  err);
-        }
-    };
-
-    // Load the image from the bytes
-    let image = image::load_from_memory(&bytes)
-        .unwrap()
-        .to_rgba16();
-
-    image
-}
+use image::{AnimationDecoder, ImageBuffer, Rgba, RgbaImage};
+use image::codecs::gif::GifDecoder;
+
+/// Delay used for gif frames that do not specify one.
+/// Browsers treat delays this small as 100ms, so we do the same.
+const MINIMUM_GIF_DELAY_MS: f32 = 20.0;
+const DEFAULT_GIF_DELAY_MS: f32 = 100.0;
+
+pub fn ImageBufferFromPath(path: &str) -> ImageBuffer<Rgba<u16>, Vec<u16>>
+{
+    // Read bytes from the file at the given path
+    let bytes = match std::fs::read(path)
+    {
+        Ok(content) => content,
+        Err(err) => {
+            panic!("Error reading file from path: {} \n {}", path,  err);
+        }
+    };
+
+    // Load the image from the bytes
+    let image = image::load_from_memory(&bytes)
+        .unwrap()
+        .to_rgba16();
+
+    image
+}
+
+/// An animated gif decoded and packed into a single sprite sheet.
+pub struct GifSheet
+{
+    /// All frames laid out left to right, top to bottom.
+    pub Image: ImageBuffer<Rgba<u16>, Vec<u16>>,
+
+    /// Total number of frames in the gif.
+    pub FrameCount: u16,
+
+    /// Number of cells horizontally and vertically in the sheet.
+    pub CellCounts: (u16, u16),
+
+    /// Display time of each frame in milliseconds.
+    pub FrameDurations: Vec<f32>
+}
+
+/// Decodes every frame of a gif and packs them into a sprite sheet
+/// that can be sampled the same way as any other sheet.
+pub fn GifSheetFromPath(path: &str) -> GifSheet
+{
+    let file = match std::fs::File::open(path)
+    {
+        Ok(content) => content,
+        Err(err) => {
+            panic!("Error reading file from path: {} \n {}", path,  err);
+        }
+    };
+
+    let decoder = match GifDecoder::new(std::io::BufReader::new(file))
+    {
+        Ok(decoder) => decoder,
+        Err(err) => {
+            panic!("Error decoding gif: {} \n {}", path, err);
+        }
+    };
+
+    let frames = decoder
+        .into_frames()
+        .collect_frames()
+        .unwrap();
+
+    if frames.is_empty()
+    {
+        panic!("Gif contains no frames: {}", path);
+    }
+
+    // Frames are composited to the full canvas by the decoder,
+    // so every frame shares the same dimensions.
+    let (frameWidth, frameHeight) = frames[0].buffer().dimensions();
+    let frameCount = frames.len() as u32;
+
+    // Keep the sheet roughly square to stay within texture size limits.
+    let columns = (frameCount as f32).sqrt().ceil() as u32;
+    let rows = (frameCount + columns - 1) / columns;
+
+    let mut sheet = RgbaImage::new(frameWidth * columns, frameHeight * rows);
+    let mut durations = Vec::with_capacity(frames.len());
+
+    for (i, frame) in frames.iter().enumerate()
+    {
+        let column = i as u32 % columns;
+        let row = i as u32 / columns;
+
+        image::imageops::replace(
+            &mut sheet,
+            frame.buffer(),
+            (column * frameWidth) as i64,
+            (row * frameHeight) as i64);
+
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let mut delay = numerator as f32 / denominator.max(1) as f32;
+
+        if delay < MINIMUM_GIF_DELAY_MS
+        {
+            delay = DEFAULT_GIF_DELAY_MS;
+        }
+
+        durations.push(delay);
+    }
+
+    GifSheet
+    {
+        Image: image::DynamicImage::ImageRgba8(sheet).to_rgba16(),
+        FrameCount: frameCount as u16,
+        CellCounts: (columns as u16, rows as u16),
+        FrameDurations: durations
+    }
+}
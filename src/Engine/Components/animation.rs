@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use crate::Engine::Components::RenderComponents::Sprite;
+
+/// Raised when an animation enters a frame that has an event attached.
+#[derive(Clone, Debug)]
+pub struct AnimationEvent
+{
+    /// Name of the event, i.e. "footstep" or "hit_active".
+    pub Name: String
+}
+
+/// A single frame of an animation clip.
+#[derive(Clone)]
+pub struct AnimationFrame
+{
+    /// Cell of the sprite sheet to display.
+    pub Index: u16,
+
+    /// Time in milliseconds this frame is displayed for.
+    pub Duration: f32,
+
+    /// Event raised when this frame is entered.
+    pub Event: Option<String>
+}
+
+/// A sequence of sprite sheet frames with individual durations.
+pub struct AnimationClip
+{
+    pub Name: String,
+    pub Sprite: Arc<Sprite>,
+    pub Frames: Vec<AnimationFrame>,
+    pub Loops: bool
+}
+
+impl AnimationClip
+{
+    /// Creates a clip that plays every frame of the sprite in order.
+    /// Frame durations come from the sprite, either its per-frame
+    /// durations or its animation speed.
+    pub fn FromSprite(name: &str, sprite: Arc<Sprite>, loops: bool) -> Self
+    {
+        let frames = (0..sprite.FrameCount)
+            .map(|i| AnimationFrame
+            {
+                Index: i,
+                Duration:
+                    if sprite.FrameDurations.is_empty()
+                    {
+                        1.0 / sprite.AnimationSpeed
+                    }
+                    else
+                    {
+                        sprite.FrameDurations[i as usize]
+                    },
+                Event: None
+            })
+            .collect();
+
+        Self
+        {
+            Name: String::from(name),
+            Sprite: sprite,
+            Frames: frames,
+            Loops: loops
+        }
+    }
+
+    /// Overrides the duration of each frame in milliseconds.
+    /// Frames without a matching entry keep their current duration.
+    pub fn WithFrameDurations(mut self, durations: &[f32]) -> Self
+    {
+        for (frame, duration) in self.Frames.iter_mut().zip(durations)
+        {
+            frame.Duration = *duration;
+        }
+
+        self
+    }
+
+    /// Attaches an event to a frame of this clip.
+    pub fn WithEvent(mut self, frame: u16, eventName: &str) -> Self
+    {
+        match self.Frames.get_mut(frame as usize)
+        {
+            Some(x) => x.Event = Some(String::from(eventName)),
+            None => panic!("Clip {} has no frame {}", self.Name, frame)
+        }
+
+        self
+    }
+}
+
+/// Plays an animation clip using game time and raises frame events.
+pub struct AnimationPlayer
+{
+    /// Multiplier applied on top of game time.
+    pub Speed: f32,
+
+    _clip: Arc<AnimationClip>,
+    _frame: usize,
+    _frameTime: f32,
+    _completed: bool,
+
+    /// Set when a frame is entered but its event has not been raised yet.
+    _hasEnteredFrame: bool,
+
+    _firedEvents: Vec<AnimationEvent>,
+    _subscribers: Vec<(String, Box<dyn FnMut(&AnimationEvent)>)>
+}
+
+impl AnimationPlayer
+{
+    pub fn Create(clip: Arc<AnimationClip>) -> Self
+    {
+        Self
+        {
+            Speed: 1.0,
+            _clip: clip,
+            _frame: 0,
+            _frameTime: 0.0,
+            _completed: false,
+            _hasEnteredFrame: true,
+            _firedEvents: Vec::new(),
+            _subscribers: Vec::new()
+        }
+    }
+
+    /// Starts playing a clip from its first frame.
+    pub fn Play(&mut self, clip: Arc<AnimationClip>)
+    {
+        self._clip = clip;
+        self._frame = 0;
+        self._frameTime = 0.0;
+        self._completed = false;
+        self._hasEnteredFrame = true;
+    }
+
+    /// Moves the animation forward by the provided game time in milliseconds.
+    /// Events of every frame entered are raised in order, even when
+    /// several frames are passed in a single step.
+    pub fn Advance(&mut self, deltaTime_Milliseconds: f32)
+    {
+        self._firedEvents.clear();
+
+        if self._clip.Frames.is_empty()
+        {
+            return;
+        }
+
+        if self._hasEnteredFrame
+        {
+            self._hasEnteredFrame = false;
+            self.RaiseFrameEvent();
+        }
+
+        if self._completed
+        {
+            return;
+        }
+
+        self._frameTime += deltaTime_Milliseconds * self.Speed;
+
+        loop
+        {
+            let duration = self._clip.Frames[self._frame].Duration;
+
+            if duration <= 0.0 || self._frameTime < duration
+            {
+                break;
+            }
+
+            self._frameTime -= duration;
+
+            if self._frame + 1 < self._clip.Frames.len()
+            {
+                self._frame += 1;
+            }
+            else if self._clip.Loops
+            {
+                self._frame = 0;
+            }
+            else
+            {
+                self._frameTime = 0.0;
+                self._completed = true;
+                break;
+            }
+
+            self.RaiseFrameEvent();
+        }
+    }
+
+    fn RaiseFrameEvent(&mut self)
+    {
+        let frame = &self._clip.Frames[self._frame];
+
+        let name = match &frame.Event
+        {
+            Some(x) => x,
+            None => return
+        };
+
+        let event = AnimationEvent
+        {
+            Name: name.clone()
+        };
+
+        for (eventName, callback) in self._subscribers.iter_mut()
+        {
+            if *eventName == event.Name
+            {
+                callback(&event);
+            }
+        }
+
+        self._firedEvents.push(event);
+    }
+
+    /// Calls the callback every time an event with the given name is raised.
+    pub fn Subscribe(&mut self, eventName: &str, callback: Box<dyn FnMut(&AnimationEvent)>)
+    {
+        self._subscribers.push((String::from(eventName), callback));
+    }
+
+    /// Returns true if an event with the given name was raised during the most recent advance.
+    pub fn HasEventFired(&self, eventName: &str) -> bool
+    {
+        self._firedEvents.iter().any(|x| x.Name == eventName)
+    }
+
+    /// Clip currently playing.
+    pub fn Clip(&self) -> &Arc<AnimationClip>
+    {
+        &self._clip
+    }
+
+    /// Cell of the sprite sheet that should be displayed.
+    pub fn SheetIndex(&self) -> u16
+    {
+        match self._clip.Frames.get(self._frame)
+        {
+            Some(x) => x.Index,
+            None => 0
+        }
+    }
+
+    /// Returns true if this is a non looped clip and it has finished playing.
+    pub fn IsComplete(&self) -> bool
+    {
+        self._completed
+    }
+}
@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use glium::Display;
+use glium::glutin::surface::WindowSurface;
+
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Components::animation::AnimationClip;
+use crate::Engine::Components::RenderComponents::Renderer2D;
+use crate::Engine::SceneBuilder::PROPERTY_SEPARATOR;
+
+/// Condition keyword that passes once a non looping clip has finished.
+const COMPLETE_CONDITION: &str = "complete";
+
+/// Source state that matches every state.
+const ANY_STATE: &str = "*";
+
+/// Value of a parameter that transitions can be conditioned on.
+#[derive(Copy, Clone, Debug)]
+pub enum EAnimatorParameter
+{
+    Bool(bool),
+    Float(f32),
+
+    /// Set for a single evaluation, consumed by the transition that uses it.
+    Trigger(bool)
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum EComparison
+{
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual
+}
+
+enum ECondition
+{
+    /// Bool parameter equals value, or trigger parameter is set.
+    Flag(String, bool),
+
+    /// Float parameter compared against a constant.
+    Compare(String, EComparison, f32),
+
+    /// Current clip has finished playing.
+    Complete
+}
+
+struct AnimatorState
+{
+    Name: String,
+    Clip: Arc<AnimationClip>,
+
+    /// Non interruptible states only evaluate transitions once their clip completes.
+    Interruptible: bool
+}
+
+struct AnimatorTransition
+{
+    /// None if this transition can be taken from any state.
+    From: Option<usize>,
+    To: usize,
+    Conditions: Vec<ECondition>
+}
+
+/// State machine that picks which animation clip a Renderer2D plays.
+///
+/// States, clips, parameters and transitions are defined in an animator file.
+/// Transitions are evaluated in file order and the first one whose
+/// conditions pass is taken.
+pub struct Animator
+{
+    _states: Vec<AnimatorState>,
+    _transitions: Vec<AnimatorTransition>,
+    _parameters: HashMap<String, EAnimatorParameter>,
+    _currentState: usize,
+
+    /// Set when the current state has not been pushed to the renderer yet.
+    _hasStateChanged: bool
+}
+
+impl Animator
+{
+    /// Loads an animator definition from file.
+    ///
+    /// File Structure:
+    ///     Clip | [name] | [sprite path] | [frame count] | [cells x, y] | [frame durations ms] | [loop/once] | [frame:event, ...] | [normal map path]
+    ///     Parameter | [name] | [bool/float/trigger] | [default]
+    ///     State | [name] | [clip] | [interruptible (true/false)]
+    ///     Transition | [from state or *] | [to state] | [condition & condition ...]
+    ///
+    /// Conditions are either a parameter name (`grounded`, `!grounded`, `punch`),
+    /// a comparison (`speed > 15`) or `complete`.
+    /// The first state listed is the entry state.
+    pub fn FromFile(path: &str, display: &Display<WindowSurface>, assets: &mut AssetManager) -> Self
+    {
+        let contents = match fs::read_to_string(path)
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read animator file: {} \n {}", path, err)
+        };
+
+        Self::Parse(path, &contents, display, assets)
+    }
+
+    fn Parse(path: &str, contents: &str, display: &Display<WindowSurface>, assets: &mut AssetManager) -> Self
+    {
+        let mut clips: HashMap<String, Arc<AnimationClip>> = HashMap::new();
+        let mut parameters: HashMap<String, EAnimatorParameter> = HashMap::new();
+        let mut states: Vec<AnimatorState> = Vec::new();
+        let mut transitionLines: Vec<Vec<String>> = Vec::new();
+
+        let entries = contents
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty() && !x.starts_with("//"));
+
+        for entry in entries
+        {
+            let tokens: Vec<String> = entry
+                .split(PROPERTY_SEPARATOR)
+                .map(|x| String::from(x.trim()))
+                .collect();
+
+            match tokens[0].as_str()
+            {
+                "Clip" =>
+                    {
+                        let clip = Self::ParseClip(path, &tokens, display, assets);
+                        clips.insert(clip.Name.clone(), Arc::new(clip));
+                    },
+
+                "Parameter" =>
+                    {
+                        parameters.insert(tokens[1].clone(), Self::ParseParameter(path, &tokens));
+                    },
+
+                "State" =>
+                    {
+                        let clip = match clips.get(&tokens[2])
+                        {
+                            Some(x) => x.clone(),
+                            None => panic!("{}: State {} uses unknown clip {}", path, tokens[1], tokens[2])
+                        };
+
+                        let interruptible = match tokens.get(3)
+                        {
+                            Some(x) => x.parse().unwrap(),
+                            None => true
+                        };
+
+                        states.push(
+                            AnimatorState
+                            {
+                                Name: tokens[1].clone(),
+                                Clip: clip,
+                                Interruptible: interruptible
+                            });
+                    },
+
+                // States may be declared after the transitions that use them.
+                "Transition" => transitionLines.push(tokens),
+
+                x => panic!("{}: Unknown animator entry {}", path, x)
+            }
+        }
+
+        if states.is_empty()
+        {
+            panic!("{}: Animator has no states", path);
+        }
+
+        let transitions = transitionLines
+            .iter()
+            .map(|x| Self::ParseTransition(path, x, &states, &parameters))
+            .collect();
+
+        Self
+        {
+            _states: states,
+            _transitions: transitions,
+            _parameters: parameters,
+            _currentState: 0,
+            _hasStateChanged: true
+        }
+    }
+
+    fn ParseClip(
+        path: &str,
+        tokens: &Vec<String>,
+        display: &Display<WindowSurface>,
+        assets: &mut AssetManager) -> AnimationClip
+    {
+        if tokens.len() < 7
+        {
+            panic!("{}: Clip {} is missing properties", path, tokens[1]);
+        }
+
+        let frameCount: u16 = tokens[3].parse().unwrap();
+
+        let cells: Vec<u16> = tokens[4]
+            .split(",")
+            .map(|x| x.trim().parse().unwrap())
+            .collect();
+
+        let durations: Vec<f32> = tokens[5]
+            .split(",")
+            .map(|x| x.trim().parse().unwrap())
+            .collect();
+
+        let loops = match tokens[6].as_str()
+        {
+            "loop" => true,
+            "once" => false,
+            x => panic!("{}: Clip {} has unknown play mode {}", path, tokens[1], x)
+        };
+
+        // A single duration applies to every frame.
+        let frameDurations =
+            if durations.len() == 1
+            {
+                vec![durations[0]; frameCount as usize]
+            }
+            else
+            {
+                durations
+            };
+
+        let sprite = match tokens.get(8).filter(|x| !x.is_empty())
+        {
+            Some(normalMapPath) => assets.NormalMappedSprite(
+                tokens[2].as_str(),
+                normalMapPath.as_str(),
+                display,
+                frameCount,
+                (cells[0], cells[1]),
+                1.0 / frameDurations[0]),
+            None => assets.Sprite(
+                tokens[2].as_str(),
+                display,
+                frameCount,
+                (cells[0], cells[1]),
+                1.0 / frameDurations[0])
+        };
+
+        let mut clip = AnimationClip::FromSprite(tokens[1].as_str(), sprite, loops)
+            .WithFrameDurations(&frameDurations);
+
+        if let Some(events) = tokens.get(7)
+        {
+            for event in events.split(",").filter(|x| !x.trim().is_empty())
+            {
+                let (frame, name) = match event.split_once(":")
+                {
+                    Some(x) => x,
+                    None => panic!("{}: Clip {} has malformed event {}", path, tokens[1], event)
+                };
+
+                clip = clip.WithEvent(frame.trim().parse().unwrap(), name.trim());
+            }
+        }
+
+        clip
+    }
+
+    fn ParseParameter(path: &str, tokens: &Vec<String>) -> EAnimatorParameter
+    {
+        let default = tokens.get(3);
+
+        match tokens[2].as_str()
+        {
+            "bool" => EAnimatorParameter::Bool(
+                default.map_or(false, |x| x.parse().unwrap())),
+
+            "float" => EAnimatorParameter::Float(
+                default.map_or(0.0, |x| x.parse().unwrap())),
+
+            "trigger" => EAnimatorParameter::Trigger(false),
+
+            x => panic!("{}: Parameter {} has unknown type {}", path, tokens[1], x)
+        }
+    }
+
+    fn ParseTransition(
+        path: &str,
+        tokens: &Vec<String>,
+        states: &Vec<AnimatorState>,
+        parameters: &HashMap<String, EAnimatorParameter>) -> AnimatorTransition
+    {
+        let findState = |name: &str| -> usize
+        {
+            match states.iter().position(|x| x.Name == name)
+            {
+                Some(x) => x,
+                None => panic!("{}: Transition uses unknown state {}", path, name)
+            }
+        };
+
+        let from =
+            if tokens[1] == ANY_STATE
+            {
+                None
+            }
+            else
+            {
+                Some(findState(tokens[1].as_str()))
+            };
+
+        let conditions = match tokens.get(3)
+        {
+            Some(x) => x
+                .split("&")
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+                .map(|x| Self::ParseCondition(path, x, parameters))
+                .collect(),
+            None => Vec::new()
+        };
+
+        AnimatorTransition
+        {
+            From: from,
+            To: findState(tokens[2].as_str()),
+            Conditions: conditions
+        }
+    }
+
+    fn ParseCondition(
+        path: &str,
+        condition: &str,
+        parameters: &HashMap<String, EAnimatorParameter>) -> ECondition
+    {
+        if condition == COMPLETE_CONDITION
+        {
+            return ECondition::Complete;
+        }
+
+        // Longer operators first so ">=" is not read as ">".
+        let operators =
+            [
+                (">=", EComparison::GreaterOrEqual),
+                ("<=", EComparison::LessOrEqual),
+                ("==", EComparison::Equal),
+                ("!=", EComparison::NotEqual),
+                (">", EComparison::Greater),
+                ("<", EComparison::Less),
+            ];
+
+        for (symbol, comparison) in operators
+        {
+            if let Some((name, value)) = condition.split_once(symbol)
+            {
+                let name = name.trim();
+                Self::ValidateParameter(path, name, parameters);
+
+                return ECondition::Compare(
+                    String::from(name),
+                    comparison,
+                    value.trim().parse().unwrap());
+            }
+        }
+
+        let (name, expected) = match condition.strip_prefix("!")
+        {
+            Some(x) => (x.trim(), false),
+            None => (condition, true)
+        };
+
+        Self::ValidateParameter(path, name, parameters);
+
+        ECondition::Flag(String::from(name), expected)
+    }
+
+    fn ValidateParameter(path: &str, name: &str, parameters: &HashMap<String, EAnimatorParameter>)
+    {
+        if !parameters.contains_key(name)
+        {
+            panic!("{}: Condition uses undeclared parameter {}", path, name);
+        }
+    }
+
+    // Parameters
+
+    pub fn SetBool(&mut self, name: &str, value: bool)
+    {
+        self.SetParameter(name, EAnimatorParameter::Bool(value));
+    }
+
+    pub fn SetFloat(&mut self, name: &str, value: f32)
+    {
+        self.SetParameter(name, EAnimatorParameter::Float(value));
+    }
+
+    /// Sets a trigger for the next evaluation.
+    pub fn SetTrigger(&mut self, name: &str)
+    {
+        self.SetParameter(name, EAnimatorParameter::Trigger(true));
+    }
+
+    fn SetParameter(&mut self, name: &str, value: EAnimatorParameter)
+    {
+        match self._parameters.get_mut(name)
+        {
+            Some(x) => *x = value,
+            None => panic!("Animator has no parameter {}", name)
+        }
+    }
+
+    // Properties
+
+    /// Clip of the entry state, useful as the initial sprite of a renderer.
+    pub fn EntryClip(&self) -> Arc<AnimationClip>
+    {
+        self._states[0].Clip.clone()
+    }
+
+    /// Returns true while a non interruptible state has not finished playing.
+    pub fn IsLocked(&self, renderer: &Renderer2D) -> bool
+    {
+        !self._states[self._currentState].Interruptible && !renderer.IsComplete()
+    }
+
+    /// Evaluates transitions and plays the clip of the resulting state.
+    /// Should be called after parameters are set for the frame.
+    pub fn Update(&mut self, renderer: &mut Renderer2D)
+    {
+        if !self.IsLocked(renderer)
+        {
+            self.EvaluateTransitions(renderer.IsComplete());
+        }
+
+        // Unused triggers do not carry over to the next frame.
+        for parameter in self._parameters.values_mut()
+        {
+            if let EAnimatorParameter::Trigger(_) = parameter
+            {
+                *parameter = EAnimatorParameter::Trigger(false);
+            }
+        }
+
+        if self._hasStateChanged
+        {
+            self._hasStateChanged = false;
+            renderer.PlayClip(self._states[self._currentState].Clip.clone());
+        }
+    }
+
+    fn EvaluateTransitions(&mut self, isComplete: bool)
+    {
+        let current = self._currentState;
+
+        let taken = self._transitions
+            .iter()
+            .find(|x|
+                (x.From.is_none() || x.From == Some(current)) &&
+                x.Conditions.iter().all(|c| self.IsConditionMet(c, isComplete)));
+
+        let transition = match taken
+        {
+            Some(x) => x,
+            None => return
+        };
+
+        // Staying in a looping state should not restart its clip,
+        // but a finished one shot can be played again.
+        let isRestartable =
+            !self._states[current].Interruptible || !self._states[current].Clip.Loops;
+
+        if transition.To == current && !(isRestartable && isComplete)
+        {
+            return;
+        }
+
+        let to = transition.To;
+
+        // Consume triggers used by the transition.
+        let usedTriggers: Vec<String> = transition.Conditions
+            .iter()
+            .filter_map(|x| match x
+            {
+                ECondition::Flag(name, true) => Some(name.clone()),
+                _ => None
+            })
+            .collect();
+
+        for name in usedTriggers
+        {
+            if let Some(EAnimatorParameter::Trigger(value)) = self._parameters.get_mut(&name)
+            {
+                *value = false;
+            }
+        }
+
+        self._currentState = to;
+        self._hasStateChanged = true;
+    }
+
+    fn IsConditionMet(&self, condition: &ECondition, isComplete: bool) -> bool
+    {
+        match condition
+        {
+            ECondition::Complete => isComplete,
+
+            ECondition::Flag(name, expected) => match self._parameters[name]
+            {
+                EAnimatorParameter::Bool(x) => x == *expected,
+                EAnimatorParameter::Trigger(x) => x == *expected,
+                EAnimatorParameter::Float(x) => (x != 0.0) == *expected
+            },
+
+            ECondition::Compare(name, comparison, value) =>
+                {
+                    let parameter = match self._parameters[name]
+                    {
+                        EAnimatorParameter::Float(x) => x,
+                        EAnimatorParameter::Bool(x) => if x {1.0} else {0.0},
+                        EAnimatorParameter::Trigger(x) => if x {1.0} else {0.0}
+                    };
+
+                    match comparison
+                    {
+                        EComparison::Greater => parameter > *value,
+                        EComparison::GreaterOrEqual => parameter >= *value,
+                        EComparison::Less => parameter < *value,
+                        EComparison::LessOrEqual => parameter <= *value,
+                        EComparison::Equal => parameter == *value,
+                        EComparison::NotEqual => parameter != *value
+                    }
+                }
+        }
+    }
+}
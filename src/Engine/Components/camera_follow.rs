@@ -0,0 +1,183 @@
+use uuid::Uuid;
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameEntity::EntityHeader;
+use crate::Engine::Math::Float3;
+
+/// Moves a camera after a target entity.
+///
+/// The target can move freely inside the deadzone without the camera reacting.
+/// Once it leaves, the camera eases towards it, offset in the direction
+/// of travel by the look ahead, and stops at the level bounds.
+pub struct CameraFollow
+{
+    /// Entity being followed. The camera stays put while None.
+    pub Target: Option<Uuid>,
+
+    /// How quickly the camera catches up, higher is snappier.
+    pub Damping: f32,
+
+    /// Width and height of the area around the focus the target can move in freely.
+    pub Deadzone: Float3,
+
+    /// Offset towards the direction the target is moving, per axis.
+    pub LookAhead: Float3,
+
+    /// How quickly the look ahead swings when the target turns around.
+    pub LookAheadDamping: f32,
+
+    /// Minimum and maximum corners the view is kept inside of.
+    pub Bounds: Option<(Float3, Float3)>,
+
+    _focus: Option<Float3>,
+    _lookAhead: Float3,
+    _lastTargetPosition: Option<Float3>
+}
+
+impl CameraFollow
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            Target: None,
+            Damping: 6.0,
+            Deadzone: Float3::new(32.0, 24.0, 0.0),
+            LookAhead: Float3::new(24.0, 0.0, 0.0),
+            LookAheadDamping: 2.0,
+            Bounds: None,
+            _focus: None,
+            _lookAhead: Float3::zero(),
+            _lastTargetPosition: None
+        }
+    }
+
+    /// Starts following a new entity. The camera eases over from where it is.
+    pub fn SetTarget(&mut self, target: Option<Uuid>)
+    {
+        self.Target = target;
+
+        // Avoid reading the jump between targets as movement.
+        self._lastTargetPosition = None;
+    }
+
+    /// Moves the camera towards the target.
+    ///
+    /// targetPosition - Current position of the target, None if it no longer exists.
+    /// viewHalfSize - Half of the visible area in world units, used to keep the view inside the bounds.
+    pub fn Update(
+        &mut self,
+        camera: &mut EntityHeader,
+        targetPosition: Option<Float3>,
+        viewHalfSize: (f32, f32),
+        frame: &GameFrame)
+    {
+        let targetPosition = match (self.Target, targetPosition)
+        {
+            (Some(_), Some(x)) => x,
+            _ => return
+        };
+
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+
+        if deltaSeconds <= 0.0
+        {
+            return;
+        }
+
+        let focus = self.UpdateFocus(camera, targetPosition);
+        self.UpdateLookAhead(targetPosition, deltaSeconds);
+
+        let desired = focus + self._lookAhead;
+
+        // Frame rate independent easing.
+        let t = 1.0 - (-self.Damping * deltaSeconds).exp();
+
+        let mut position = Float3::Lerp(camera.WorldPosition, desired, t)
+            .OverrideZ(camera.WorldPosition.z());
+
+        if let Some(bounds) = self.Bounds
+        {
+            position = Self::Clamp(position, bounds, viewHalfSize);
+        }
+
+        camera.WorldPosition = position;
+    }
+
+    /// Drags the focus point along once the target pushes against the deadzone edge.
+    fn UpdateFocus(&mut self, camera: &EntityHeader, targetPosition: Float3) -> Float3
+    {
+        let mut focus = match self._focus
+        {
+            Some(x) => x,
+            None => camera.WorldPosition - self._lookAhead
+        };
+
+        let halfWidth = self.Deadzone.x() * 0.5;
+        let halfHeight = self.Deadzone.y() * 0.5;
+
+        let dx = targetPosition.x() - focus.x();
+        let dy = targetPosition.y() - focus.y();
+
+        if dx.abs() > halfWidth
+        {
+            focus.AddX(dx - halfWidth * dx.signum());
+        }
+
+        if dy.abs() > halfHeight
+        {
+            focus.AddY(dy - halfHeight * dy.signum());
+        }
+
+        self._focus = Some(focus);
+
+        focus
+    }
+
+    /// Swings the look ahead towards the direction the target last moved in.
+    fn UpdateLookAhead(&mut self, targetPosition: Float3, deltaSeconds: f32)
+    {
+        let movement = match self._lastTargetPosition
+        {
+            Some(x) => targetPosition - x,
+            None => Float3::zero()
+        };
+
+        self._lastTargetPosition = Some(targetPosition);
+
+        // Holding still keeps the last direction instead of recentering.
+        let direction = |delta: f32, current: f32, distance: f32|
+            {
+                if delta.abs() > 0.01 { delta.signum() * distance } else { current }
+            };
+
+        let desired = Float3::new(
+            direction(movement.x(), self._lookAhead.x(), self.LookAhead.x()),
+            direction(movement.y(), self._lookAhead.y(), self.LookAhead.y()),
+            0.0);
+
+        let t = 1.0 - (-self.LookAheadDamping * deltaSeconds).exp();
+        self._lookAhead = Float3::Lerp(self._lookAhead, desired, t);
+    }
+
+    /// Keeps the view inside bounds, centering on any axis the view is larger than.
+    fn Clamp(position: Float3, bounds: (Float3, Float3), viewHalfSize: (f32, f32)) -> Float3
+    {
+        let clampAxis = |value: f32, min: f32, max: f32, halfSize: f32|
+            {
+                if max - min <= halfSize * 2.0
+                {
+                    (min + max) * 0.5
+                }
+                else
+                {
+                    value.clamp(min + halfSize, max - halfSize)
+                }
+            };
+
+        Float3::new(
+            clampAxis(position.x(), bounds.0.x(), bounds.1.x(), viewHalfSize.0),
+            clampAxis(position.y(), bounds.0.y(), bounds.1.y(), viewHalfSize.1),
+            position.z())
+    }
+}
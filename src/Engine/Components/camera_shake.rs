@@ -0,0 +1,147 @@
+use std::f32::consts::TAU;
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::Math::Float3;
+
+/// A zoom change that eases back out over its duration, in seconds.
+struct ZoomPunch
+{
+    Amount: f32,
+    Duration: f32,
+    Elapsed: f32
+}
+
+/// Short lived camera offsets used for impact feedback.
+///
+/// Shake is driven by trauma between 0 and 1 that decays over time.
+/// The strength of the shake is trauma squared, so small hits barely
+/// register while big ones stack up quickly.
+/// Nothing here moves the camera entity itself, the offsets are only
+/// applied when building the view.
+pub struct CameraShake
+{
+    /// Largest positional offset in world units at full trauma.
+    pub MaxOffset: f32,
+
+    /// Largest roll in degrees at full trauma.
+    pub MaxRotation: f32,
+
+    /// Trauma lost per second.
+    pub Decay: f32,
+
+    /// How fast the shake moves, in oscillations per second.
+    pub Frequency: f32,
+
+    /// How quickly kicks return to rest, higher is faster.
+    pub KickDamping: f32,
+
+    _trauma: f32,
+    _time: f32,
+    _kick: Float3,
+    _zoomPunches: Vec<ZoomPunch>
+}
+
+impl CameraShake
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            MaxOffset: 6.0,
+            MaxRotation: 3.0,
+            Decay: 1.5,
+            Frequency: 18.0,
+            KickDamping: 14.0,
+            _trauma: 0.0,
+            _time: 0.0,
+            _kick: Float3::zero(),
+            _zoomPunches: Vec::new()
+        }
+    }
+
+    /// Adds trauma, capped at 1.
+    pub fn AddTrauma(&mut self, amount: f32)
+    {
+        self._trauma = (self._trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Pushes the view in a direction, it springs back on its own.
+    pub fn Kick(&mut self, impulse: Float3)
+    {
+        self._kick = self._kick + impulse;
+    }
+
+    /// Zooms in by amount, i.e. 0.1 for 10%, and eases back out over seconds.
+    pub fn ZoomPunch(&mut self, amount: f32, seconds: f32)
+    {
+        self._zoomPunches.push(
+            ZoomPunch
+            {
+                Amount: amount,
+                Duration: seconds.max(0.001),
+                Elapsed: 0.0
+            });
+    }
+
+    /// Advances every effect by the frame's game time.
+    pub fn Update(&mut self, frame: &GameFrame)
+    {
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+
+        self._time += deltaSeconds;
+        self._trauma = (self._trauma - self.Decay * deltaSeconds).max(0.0);
+
+        let t = 1.0 - (-self.KickDamping * deltaSeconds).exp();
+        self._kick = Float3::Lerp(self._kick, Float3::zero(), t);
+
+        for punch in self._zoomPunches.iter_mut()
+        {
+            punch.Elapsed += deltaSeconds;
+        }
+
+        self._zoomPunches.retain(|x| x.Elapsed < x.Duration);
+    }
+
+    /// Offset to add to the camera position this frame.
+    pub fn Offset(&self) -> Float3
+    {
+        let shake = self._trauma * self._trauma;
+
+        Float3::new(
+            self.MaxOffset * shake * Noise(self._time * self.Frequency, 0.0),
+            self.MaxOffset * shake * Noise(self._time * self.Frequency, 10.0),
+            0.0) + self._kick
+    }
+
+    /// Roll in radians to apply to the view this frame.
+    pub fn Rotation(&self) -> f32
+    {
+        let shake = self._trauma * self._trauma;
+
+        (self.MaxRotation * shake * Noise(self._time * self.Frequency, 20.0)).to_radians()
+    }
+
+    /// Multiplier for the camera zoom this frame.
+    pub fn ZoomMultiplier(&self) -> f32
+    {
+        1.0 + self._zoomPunches
+            .iter()
+            .map(|x|
+                {
+                    // Ease out, strongest on the first frame.
+                    let remaining = 1.0 - x.Elapsed / x.Duration;
+                    x.Amount * remaining * remaining
+                })
+            .sum::<f32>()
+    }
+}
+
+/// Smooth value between -1 and 1, offset by seed so each axis moves differently.
+/// time is in cycles, the slowest wave repeats once per whole number.
+fn Noise(time: f32, seed: f32) -> f32
+{
+    let t = time * TAU + seed;
+
+    ((t * 1.0).sin() * 0.5 + (t * 2.3 + 1.7).sin() * 0.3 + (t * 4.7 + 3.1).sin() * 0.2)
+        .clamp(-1.0, 1.0)
+}
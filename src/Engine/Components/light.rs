@@ -0,0 +1,75 @@
+use std::fmt::{Debug, Formatter};
+use uuid::Uuid;
+
+use crate::Engine::Components::RenderComponents::LightSource;
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::GameEntity::{EntityHeader, TEntity};
+use crate::Engine::Math::Float3;
+use crate::Engine::Rendering::Renderer;
+use crate::Engine::Rendering::debug_draw::EDebugSpace;
+
+/// A point or spot light placed in the scene.
+pub struct Light
+{
+    pub Header: EntityHeader,
+    pub Source: LightSource
+}
+
+impl Light
+{
+    pub fn Create(name: &str, position: Float3, source: LightSource) -> Self
+    {
+        Self
+        {
+            Header: EntityHeader::Create(name, position),
+            Source: source
+        }
+    }
+}
+
+impl Debug for Light {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "Light {}", self.Header.Name)
+    }
+}
+
+impl TEntity for Light
+{
+    fn HasStartBeenCalled(&self) -> bool
+    {
+        self.Header.HasStartBeenCalled()
+    }
+
+    fn ID(&self) -> Uuid
+    {
+        self.Header.ID()
+    }
+
+    unsafe fn Start(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    /// Outlines the reach of the light while debug drawing is enabled.
+    unsafe fn Update(&mut self, _frame: &GameFrame, api: *mut GameAPI)
+    {
+        let color = self.Source.Color;
+
+        (*api).Debug.Circle(
+            EDebugSpace::World,
+            self.Header.WorldPosition,
+            self.Source.Radius,
+            [color.x(), color.y(), color.z(), 1.0],
+            0.0);
+    }
+
+    unsafe fn OnDestroy(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    fn Render(&mut self, _frame: &GameFrame, renderer: &mut Renderer)
+    {
+        self.Source.Render(&self.Header, renderer);
+    }
+}
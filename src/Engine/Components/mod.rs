@@ -1,7 +1,15 @@
-pub mod RenderComponents;
-pub mod AudioSource;
-pub mod Collider;
-pub mod Camera;
-pub mod Bullet;
-mod RenderUtilities;
-
+pub mod RenderComponents;
+pub mod AudioSource;
+pub mod Collider;
+pub mod Camera;
+pub mod camera_follow;
+pub mod camera_shake;
+pub mod Bullet;
+pub mod animation;
+pub mod animator;
+pub mod light;
+pub mod parallax;
+pub mod particles;
+pub mod text;
+mod RenderUtilities;
+
@@ -0,0 +1,230 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use glium::Texture2d;
+use uuid::Uuid;
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::GameEntity::{EntityHeader, TEntity};
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, Renderer, SpriteQuad, DEFAULT_LIT_FRAGMENT};
+use crate::Engine::Shader::DEFAULT_FRAGMENT;
+
+/// A background or foreground image that scrolls at its own rate as the camera moves.
+///
+/// A scroll factor of 1 moves with the world, 0 stays fixed to the screen.
+/// Values in between look further away, above 1 look closer than the world.
+/// Tiled axes repeat the image to fill the view.
+/// The renderer offsets the layer for each camera drawing it, so split screen views all line up.
+pub struct ParallaxLayer
+{
+    pub Header: EntityHeader,
+    pub Texture: Arc<Texture2d>,
+
+    /// How much of the camera's movement the layer follows, per axis.
+    pub ScrollFactor: Float3,
+
+    pub TileX: bool,
+    pub TileY: bool,
+
+    /// Constant drift in world units per second, i.e. for clouds.
+    pub AutoScroll: Float3,
+
+    pub Tint: [f32; 4],
+    pub IsLit: bool,
+
+    _layer: ESortingLayer,
+    _orderInLayer: i32,
+
+    /// Distance drifted by AutoScroll so far.
+    _scrollOffset: Float3,
+
+    /// Copies drawn along each axis this frame, covering every camera drawing the layer.
+    _columns: (i32, i32),
+    _rows: (i32, i32)
+}
+
+impl ParallaxLayer
+{
+    pub fn Create(name: &str, position: Float3, texture: Arc<Texture2d>, scrollFactor: Float3) -> Self
+    {
+        Self
+        {
+            Header: EntityHeader::Create(name, position),
+            Texture: texture,
+            ScrollFactor: scrollFactor,
+            TileX: false,
+            TileY: false,
+            AutoScroll: Float3::zero(),
+            Tint: [1.0, 1.0, 1.0, 1.0],
+            IsLit: false,
+            _layer: ESortingLayer::Background,
+            _orderInLayer: 0,
+            _scrollOffset: Float3::zero(),
+            _columns: (0, 0),
+            _rows: (0, 0)
+        }
+    }
+
+    pub fn SetTiling(&mut self, tileX: bool, tileY: bool)
+    {
+        self.TileX = tileX;
+        self.TileY = tileY;
+    }
+
+    pub fn SetSortingLayer(&mut self, layer: ESortingLayer, orderInLayer: i32)
+    {
+        self._layer = layer;
+        self._orderInLayer = orderInLayer;
+    }
+
+    /// Size of one copy of the image in world units.
+    fn Size(&self) -> (f32, f32)
+    {
+        (
+            self.Texture.width() as f32 / PIXELS_PER_UNIT * self.Header.scale.x(),
+            self.Texture.height() as f32 / PIXELS_PER_UNIT * self.Header.scale.y()
+        )
+    }
+
+    /// Center of the untiled image before the renderer offsets it for a view.
+    fn Origin(&self) -> Float3
+    {
+        self.Header.WorldPosition + self._scrollOffset
+    }
+
+    /// Copies needed along one axis to cover the view, relative to the origin.
+    fn TileRange(isTiled: bool, origin: f32, size: f32, cameraPosition: f32, viewHalfSize: f32) -> (i32, i32)
+    {
+        if !isTiled || size <= 0.0
+        {
+            return (0, 0);
+        }
+
+        (
+            ((cameraPosition - viewHalfSize - origin) / size).floor() as i32 - 1,
+            ((cameraPosition + viewHalfSize - origin) / size).ceil() as i32 + 1
+        )
+    }
+}
+
+impl Debug for ParallaxLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "ParallaxLayer {}", self.Header.Name)
+    }
+}
+
+impl TEntity for ParallaxLayer
+{
+    fn HasStartBeenCalled(&self) -> bool
+    {
+        self.Header.HasStartBeenCalled()
+    }
+
+    fn ID(&self) -> Uuid
+    {
+        self.Header.ID()
+    }
+
+    unsafe fn Start(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    /// Must run after the cameras so the tiles cover this frame's views.
+    unsafe fn Update(&mut self, frame: &GameFrame, api: *mut GameAPI)
+    {
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+        self._scrollOffset = self._scrollOffset + Float3::scale_value(self.AutoScroll, deltaSeconds);
+
+        // Wrap the drift on tiled axes so it never grows large enough to lose precision.
+        let (width, height) = self.Size();
+
+        if self.TileX && width > 0.0
+        {
+            let x = self._scrollOffset.x() % width;
+            self._scrollOffset = Float3::new(x, self._scrollOffset.y(), 0.0);
+        }
+
+        if self.TileY && height > 0.0
+        {
+            let y = self._scrollOffset.y() % height;
+            self._scrollOffset = Float3::new(self._scrollOffset.x(), y, 0.0);
+        }
+
+        // Each camera sees the layer offset by its own position, tile enough to cover all of them.
+        let cameras = &(*api).SceneManager.Entities.Cameras;
+        let screen = ((*api).Screen.Width, (*api).Screen.Height);
+        let mask = self._layer.Mask();
+        let origin = self.Origin();
+
+        let mut columns: Option<(i32, i32)> = None;
+        let mut rows: Option<(i32, i32)> = None;
+
+        for camera in cameras.iter().filter(|x| !x.IsScreenSpace && x.LayerMask & mask != 0)
+        {
+            let position = camera.ViewPosition();
+            let halfSize = camera.VisibleHalfSize(camera.ViewportSize(screen));
+
+            let x = origin.x() + position.x() * (1.0 - self.ScrollFactor.x());
+            let y = origin.y() + position.y() * (1.0 - self.ScrollFactor.y());
+
+            let cameraColumns = Self::TileRange(self.TileX, x, width, position.x(), halfSize.0);
+            let cameraRows = Self::TileRange(self.TileY, y, height, position.y(), halfSize.1);
+
+            columns = Some(columns.map_or(cameraColumns, |c| (c.0.min(cameraColumns.0), c.1.max(cameraColumns.1))));
+            rows = Some(rows.map_or(cameraRows, |r| (r.0.min(cameraRows.0), r.1.max(cameraRows.1))));
+        }
+
+        self._columns = columns.unwrap_or((0, 0));
+        self._rows = rows.unwrap_or((0, 0));
+    }
+
+    unsafe fn OnDestroy(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    fn Render(&mut self, _frame: &GameFrame, renderer: &mut Renderer)
+    {
+        let (width, height) = self.Size();
+        let origin = self.Origin();
+
+        let half_width = width * 0.5;
+        let half_height = height * 0.5;
+
+        let shader = if self.IsLit {DEFAULT_LIT_FRAGMENT} else {DEFAULT_FRAGMENT};
+
+        for row in self._rows.0..=self._rows.1
+        {
+            for column in self._columns.0..=self._columns.1
+            {
+                let center = origin + Float3::new(column as f32 * width, row as f32 * height, 0.0);
+
+                renderer.Submit(
+                    SpriteQuad
+                    {
+                        Texture: self.Texture.clone(),
+                        NormalMap: None,
+                        Shader: String::from(shader),
+                        Parameters: None,
+                        Corners:
+                        [
+                            center + Float3::new(-half_width, -half_height, 0.0),
+                            center + Float3::new( half_width, -half_height, 0.0),
+                            center + Float3::new( half_width,  half_height, 0.0),
+                            center + Float3::new(-half_width,  half_height, 0.0),
+                        ],
+                        UVMin: [0.0, 0.0],
+                        UVMax: [1.0, 1.0],
+                        Tint: self.Tint,
+                        IsLit: self.IsLit,
+                        ScrollFactor: self.ScrollFactor,
+                        Layer: self._layer,
+                        OrderInLayer: self._orderInLayer
+                    });
+            }
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::sync::Arc;
+
+use glium::{Display, Texture2d};
+use glium::glutin::surface::WindowSurface;
+use uuid::Uuid;
+
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::GameEntity::{EntityHeader, TEntity};
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, Renderer, SpriteQuad, DEFAULT_LIT_FRAGMENT};
+use crate::Engine::SceneBuilder::PROPERTY_SEPARATOR;
+use crate::Engine::Shader::DEFAULT_FRAGMENT;
+
+/// Where particles live once spawned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ESimulationSpace
+{
+    /// Particles stay where they were spawned when the emitter moves, i.e. dust trails.
+    World,
+
+    /// Particles move along with the emitter, i.e. a torch flame.
+    Local
+}
+
+/// Settings shared by every emitter created from the same file.
+#[derive(Clone)]
+pub struct ParticlePreset
+{
+    pub TexturePath: String,
+    pub FrameCount: u16,
+    pub CellCounts: (u16, u16),
+
+    /// Sheet frames per second, 0 plays the frames once over each particle's lifetime.
+    pub FrameRate: f32,
+
+    /// Particles spawned per second while emitting.
+    pub Rate: f32,
+
+    /// Particles spawned at once when the emitter starts.
+    pub Burst: u32,
+    pub MaxParticles: usize,
+
+    /// Keeps emitting at Rate after the first burst.
+    pub IsLooping: bool,
+
+    /// Seconds each particle lives, picked between min and max.
+    pub Lifetime: (f32, f32),
+
+    /// Starting speed in world units per second, picked between min and max.
+    pub Speed: (f32, f32),
+
+    /// Angle particles are fired at in degrees, 0 is right and 90 is up.
+    pub Direction: f32,
+
+    /// Full angle in degrees particles are spread over around Direction.
+    pub Spread: f32,
+
+    /// Acceleration in world units per second squared.
+    pub Gravity: Float3,
+
+    /// Fraction of velocity lost per second.
+    pub Drag: f32,
+
+    pub StartColor: [f32; 4],
+    pub EndColor: [f32; 4],
+
+    /// Multiplier of the frame size in world units.
+    pub StartSize: f32,
+    pub EndSize: f32,
+
+    pub Space: ESimulationSpace,
+
+    pub Layer: ESortingLayer,
+    pub OrderInLayer: i32,
+    pub IsLit: bool
+}
+
+impl ParticlePreset
+{
+    pub fn Create(texturePath: &str) -> Self
+    {
+        Self
+        {
+            TexturePath: String::from(texturePath),
+            FrameCount: 1,
+            CellCounts: (1, 1),
+            FrameRate: 0.0,
+            Rate: 10.0,
+            Burst: 0,
+            MaxParticles: 256,
+            IsLooping: true,
+            Lifetime: (1.0, 1.0),
+            Speed: (20.0, 40.0),
+            Direction: 90.0,
+            Spread: 30.0,
+            Gravity: Float3::zero(),
+            Drag: 0.0,
+            StartColor: [1.0, 1.0, 1.0, 1.0],
+            EndColor: [1.0, 1.0, 1.0, 0.0],
+            StartSize: 1.0,
+            EndSize: 1.0,
+            Space: ESimulationSpace::World,
+            Layer: ESortingLayer::Foreground,
+            OrderInLayer: 0,
+            IsLit: false
+        }
+    }
+
+    /// Loads a preset from file. Entries left out keep their defaults.
+    ///
+    /// File Structure:
+    ///     Texture | [sprite path] | [frame count] | [cells x, y] | [frames per second]
+    ///     Emission | [rate per second] | [burst] | [max particles] | [looping (true/false)]
+    ///     Lifetime | [min seconds] | [max seconds]
+    ///     Velocity | [min speed] | [max speed] | [direction degrees] | [spread degrees]
+    ///     Gravity | [x, y, z]
+    ///     Drag | [fraction per second]
+    ///     Color | [start r, g, b, a] | [end r, g, b, a]
+    ///     Size | [start] | [end]
+    ///     Space | [World/Local]
+    ///     Layer | [sorting layer] | [order in layer] | [lit (true/false)]
+    pub fn FromFile(path: &str) -> Self
+    {
+        let contents = match fs::read_to_string(path)
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read particle preset: {} \n {}", path, err)
+        };
+
+        let mut preset = Self::Create("");
+
+        let entries = contents
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty() && !x.starts_with("//"));
+
+        for entry in entries
+        {
+            let tokens: Vec<&str> = entry
+                .split(PROPERTY_SEPARATOR)
+                .map(|x| x.trim())
+                .collect();
+
+            let float = |index: usize| -> f32
+                {
+                    match tokens.get(index)
+                    {
+                        Some(x) => x.parse().unwrap(),
+                        None => panic!("{}: {} is missing properties", path, tokens[0])
+                    }
+                };
+
+            match tokens[0]
+            {
+                "Texture" =>
+                    {
+                        preset.TexturePath = String::from(tokens[1]);
+                        preset.FrameCount = float(2) as u16;
+
+                        let cells: Vec<u16> = tokens[3].split(',').map(|x| x.trim().parse().unwrap()).collect();
+                        preset.CellCounts = (cells[0], cells[1]);
+                        preset.FrameRate = float(4);
+                    }
+                "Emission" =>
+                    {
+                        preset.Rate = float(1);
+                        preset.Burst = float(2) as u32;
+                        preset.MaxParticles = float(3) as usize;
+                        preset.IsLooping = tokens[4].parse().unwrap();
+                    }
+                "Lifetime" => preset.Lifetime = (float(1), float(2)),
+                "Velocity" =>
+                    {
+                        preset.Speed = (float(1), float(2));
+                        preset.Direction = float(3);
+                        preset.Spread = float(4);
+                    }
+                "Gravity" => preset.Gravity = Float3::FromString(tokens[1]),
+                "Drag" => preset.Drag = float(1),
+                "Color" =>
+                    {
+                        preset.StartColor = ParseColor(tokens[1]);
+                        preset.EndColor = ParseColor(tokens[2]);
+                    }
+                "Size" =>
+                    {
+                        preset.StartSize = float(1);
+                        preset.EndSize = float(2);
+                    }
+                "Space" =>
+                    {
+                        preset.Space = match tokens[1].to_lowercase().as_str()
+                        {
+                            "world" => ESimulationSpace::World,
+                            "local" => ESimulationSpace::Local,
+                            x => panic!("{}: Unknown simulation space {}", path, x)
+                        };
+                    }
+                "Layer" =>
+                    {
+                        preset.Layer = ESortingLayer::FromString(tokens[1]);
+                        preset.OrderInLayer = float(2) as i32;
+                        preset.IsLit = tokens[3].parse().unwrap();
+                    }
+                x => panic!("{}: Unknown particle entry {}", path, x)
+            }
+        }
+
+        if preset.TexturePath.is_empty()
+        {
+            panic!("{}: Particle preset has no Texture entry", path);
+        }
+
+        preset
+    }
+}
+
+/// Parses "r, g, b, a".
+fn ParseColor(value: &str) -> [f32; 4]
+{
+    let values: Vec<f32> = value.split(',').map(|x| x.trim().parse().unwrap()).collect();
+
+    if values.len() != 4
+    {
+        panic!("Particle color needs r, g, b, a: {}", value);
+    }
+
+    [values[0], values[1], values[2], values[3]]
+}
+
+struct Particle
+{
+    /// World position, or offset from the emitter in local space.
+    Position: Float3,
+    Velocity: Float3,
+    Age: f32,
+    Lifetime: f32
+}
+
+/// Small xorshift generator, particles only need cheap variety.
+struct Random
+{
+    _state: u32
+}
+
+impl Random
+{
+    fn Create() -> Self
+    {
+        Self { _state: (Uuid::new_v4().as_u128() as u32) | 1 }
+    }
+
+    /// Value between 0 and 1.
+    fn Next(&mut self) -> f32
+    {
+        self._state ^= self._state << 13;
+        self._state ^= self._state >> 17;
+        self._state ^= self._state << 5;
+
+        (self._state >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    fn Range(&mut self, range: (f32, f32)) -> f32
+    {
+        range.0 + (range.1 - range.0) * self.Next()
+    }
+}
+
+/// Spawns, moves and draws particles for an entity.
+///
+/// Particles are plain quads submitted to the renderer, so every particle
+/// from one emitter shares a texture and is drawn with a single call.
+pub struct ParticleEmitter
+{
+    pub Preset: Arc<ParticlePreset>,
+    pub Texture: Arc<Texture2d>,
+
+    /// Offset from the entity particles spawn at.
+    pub Offset: Float3,
+
+    /// Mirrors the offset and direction, i.e. to follow a flipped sprite.
+    pub FlipX: bool,
+
+    _particles: Vec<Particle>,
+    _isEmitting: bool,
+
+    /// Fractional particles carried over between frames.
+    _spawnAccumulator: f32,
+
+    /// Bursts requested since the last update.
+    _pendingBurst: u32,
+
+    _random: Random
+}
+
+impl ParticleEmitter
+{
+    /// Creates an emitter that starts emitting straight away if the preset loops.
+    pub fn Create(preset: Arc<ParticlePreset>, texture: Arc<Texture2d>) -> Self
+    {
+        let isLooping = preset.IsLooping;
+        let burst = if isLooping { preset.Burst } else { 0 };
+
+        Self
+        {
+            Preset: preset,
+            Texture: texture,
+            Offset: Float3::zero(),
+            FlipX: false,
+            _particles: Vec::new(),
+            _isEmitting: isLooping,
+            _spawnAccumulator: 0.0,
+            _pendingBurst: burst,
+            _random: Random::Create()
+        }
+    }
+
+    /// Loads a preset from file and its texture through the asset manager.
+    pub fn FromFile(path: &str, display: &Display<WindowSurface>, assets: &mut AssetManager) -> Self
+    {
+        let preset = ParticlePreset::FromFile(path);
+        let texture = assets.Texture(preset.TexturePath.as_str(), display);
+
+        Self::Create(Arc::new(preset), texture)
+    }
+
+    /// Spawns count particles on the next update, whether or not the emitter is playing.
+    pub fn Burst(&mut self, count: u32)
+    {
+        self._pendingBurst += count;
+    }
+
+    pub fn ParticleCount(&self) -> usize
+    {
+        self._particles.len()
+    }
+
+    /// Point particles spawn from, in world space.
+    fn Origin(&self, entity: &EntityHeader) -> Float3
+    {
+        entity.WorldPosition + self.Offset.Mirror(self.FlipX, false)
+    }
+
+    /// Ages, moves and spawns particles using the frame's game time.
+    pub fn Update(&mut self, entity: &EntityHeader, frame: &GameFrame)
+    {
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+
+        for particle in self._particles.iter_mut()
+        {
+            particle.Age += deltaSeconds;
+        }
+
+        self._particles.retain(|x| x.Age < x.Lifetime);
+
+        let drag = (1.0 - self.Preset.Drag * deltaSeconds).max(0.0);
+        let gravity = Float3::scale_value(self.Preset.Gravity, deltaSeconds);
+
+        for particle in self._particles.iter_mut()
+        {
+            particle.Velocity = Float3::scale_value(particle.Velocity + gravity, drag);
+            particle.Position = particle.Position + Float3::scale_value(particle.Velocity, deltaSeconds);
+        }
+
+        let mut count = self._pendingBurst;
+        self._pendingBurst = 0;
+
+        if self._isEmitting
+        {
+            self._spawnAccumulator += self.Preset.Rate * deltaSeconds;
+            count += self._spawnAccumulator as u32;
+            self._spawnAccumulator = self._spawnAccumulator.fract();
+        }
+
+        let origin = match self.Preset.Space
+        {
+            ESimulationSpace::World => self.Origin(entity),
+            ESimulationSpace::Local => Float3::zero()
+        };
+
+        for _ in 0..count
+        {
+            if self._particles.len() >= self.Preset.MaxParticles
+            {
+                break;
+            }
+
+            self.Spawn(origin);
+        }
+    }
+
+    fn Spawn(&mut self, origin: Float3)
+    {
+        let spread = self.Preset.Spread;
+        let mut angle = self.Preset.Direction + (self._random.Next() - 0.5) * spread;
+
+        if self.FlipX
+        {
+            angle = 180.0 - angle;
+        }
+
+        let speed = self._random.Range(self.Preset.Speed);
+        let radians = angle.to_radians();
+
+        self._particles.push(
+            Particle
+            {
+                Position: origin,
+                Velocity: Float3::new(radians.cos() * speed, radians.sin() * speed, 0.0),
+                Age: 0.0,
+                Lifetime: self._random.Range(self.Preset.Lifetime).max(0.001)
+            });
+    }
+
+    /// Submits every live particle to the renderer.
+    pub fn Render(&self, entity: &EntityHeader, renderer: &mut Renderer)
+    {
+        let preset = &self.Preset;
+
+        let cellCountX = preset.CellCounts.0.max(1) as f32;
+        let cellCountY = preset.CellCounts.1.max(1) as f32;
+        let frameCount = preset.FrameCount.max(1);
+
+        let cellWidth = self.Texture.width() as f32 / cellCountX / PIXELS_PER_UNIT;
+        let cellHeight = self.Texture.height() as f32 / cellCountY / PIXELS_PER_UNIT;
+
+        let offset = match preset.Space
+        {
+            ESimulationSpace::World => Float3::zero(),
+            ESimulationSpace::Local => self.Origin(entity)
+        };
+
+        let shader = if preset.IsLit {DEFAULT_LIT_FRAGMENT} else {DEFAULT_FRAGMENT};
+
+        for particle in self._particles.iter()
+        {
+            let t = (particle.Age / particle.Lifetime).clamp(0.0, 1.0);
+
+            let frame =
+                if preset.FrameRate > 0.0
+                {
+                    (particle.Age * preset.FrameRate) as u16 % frameCount
+                }
+                else
+                {
+                    ((t * frameCount as f32) as u16).min(frameCount - 1)
+                };
+
+            let column = (frame % cellCountX as u16) as f32;
+            let row = (frame / cellCountX as u16) as f32;
+
+            let size = preset.StartSize + (preset.EndSize - preset.StartSize) * t;
+            let half_width = cellWidth * size * 0.5;
+            let half_height = cellHeight * size * 0.5;
+
+            let mut tint = [0.0; 4];
+            for i in 0..4
+            {
+                tint[i] = preset.StartColor[i] + (preset.EndColor[i] - preset.StartColor[i]) * t;
+            }
+
+            let center = particle.Position + offset;
+
+            renderer.Submit(
+                SpriteQuad
+                {
+                    Texture: self.Texture.clone(),
+                    NormalMap: None,
+                    Shader: String::from(shader),
+                    Parameters: None,
+                    Corners:
+                    [
+                        center + Float3::new(-half_width, -half_height, 0.0),
+                        center + Float3::new( half_width, -half_height, 0.0),
+                        center + Float3::new( half_width,  half_height, 0.0),
+                        center + Float3::new(-half_width,  half_height, 0.0),
+                    ],
+                    UVMin: [column / cellCountX, 1.0 - (row + 1.0) / cellCountY],
+                    UVMax: [(column + 1.0) / cellCountX, 1.0 - row / cellCountY],
+                    Tint: tint,
+                    IsLit: preset.IsLit,
+                    ScrollFactor: Float3::one(),
+                    Layer: preset.Layer,
+                    OrderInLayer: preset.OrderInLayer
+                });
+        }
+    }
+}
+
+/// A particle emitter placed in the scene on its own, i.e. a waterfall splash.
+pub struct ParticleEffect
+{
+    pub Header: EntityHeader,
+    pub Emitter: ParticleEmitter
+}
+
+impl ParticleEffect
+{
+    pub fn Create(name: &str, position: Float3, emitter: ParticleEmitter) -> Self
+    {
+        Self
+        {
+            Header: EntityHeader::Create(name, position),
+            Emitter: emitter
+        }
+    }
+}
+
+impl Debug for ParticleEffect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "ParticleEffect {}", self.Header.Name)
+    }
+}
+
+impl TEntity for ParticleEffect
+{
+    fn HasStartBeenCalled(&self) -> bool
+    {
+        self.Header.HasStartBeenCalled()
+    }
+
+    fn ID(&self) -> Uuid
+    {
+        self.Header.ID()
+    }
+
+    unsafe fn Start(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    unsafe fn Update(&mut self, frame: &GameFrame, _api: *mut GameAPI)
+    {
+        self.Emitter.Update(&self.Header, frame);
+    }
+
+    unsafe fn OnDestroy(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    fn Render(&mut self, _frame: &GameFrame, renderer: &mut Renderer)
+    {
+        self.Emitter.Render(&self.Header, renderer);
+    }
+}
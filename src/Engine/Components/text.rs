@@ -0,0 +1,143 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::GameEntity::{EntityHeader, TEntity};
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, Renderer};
+use crate::Engine::Rendering::debug_draw::{DebugDraw, EDebugSpace};
+use crate::Engine::Rendering::text::{ETextAlign, Font, TextStyle};
+
+/// Color of label bounds while debug drawing is enabled.
+const BOUNDS_COLOR: [f32; 4] = [0.8, 0.4, 1.0, 1.0];
+
+/// Draws a string at its entity every frame.
+pub struct TextRenderer
+{
+    pub Font: Arc<Font>,
+    pub Text: String,
+    pub Style: TextStyle,
+
+    /// Offset from the entity to the text position.
+    pub Offset: Float3
+}
+
+impl TextRenderer
+{
+    pub fn New(font: Arc<Font>, text: &str, style: TextStyle) -> Self
+    {
+        Self
+        {
+            Font: font,
+            Text: String::from(text),
+            Style: style,
+            Offset: Float3::zero()
+        }
+    }
+
+    /// Width and height of the current text in world units.
+    pub fn Size(&self) -> (f32, f32)
+    {
+        self.Font.Measure(self.Text.as_str(), &self.Style)
+    }
+
+    /// Outlines the area the text covers, in screen pixels on the UI layer.
+    pub fn DrawBounds(&self, entity: &EntityHeader, debug: &mut DebugDraw)
+    {
+        let (width, height) = self.Size();
+        let anchor = entity.WorldPosition + self.Offset;
+
+        let left = match self.Style.Align
+        {
+            ETextAlign::Left => anchor.x(),
+            ETextAlign::Center => anchor.x() - width * 0.5,
+            ETextAlign::Right => anchor.x() - width
+        };
+
+        let center = Float3::new(left + width * 0.5, anchor.y() - height * 0.5, anchor.z());
+
+        if self.Style.Layer == ESortingLayer::UI
+        {
+            debug.Rect(
+                EDebugSpace::Screen,
+                Float3::scale_value(center, PIXELS_PER_UNIT),
+                Float3::new(width * PIXELS_PER_UNIT, height * PIXELS_PER_UNIT, 0.0),
+                BOUNDS_COLOR,
+                0.0);
+        }
+        else
+        {
+            debug.Rect(EDebugSpace::World, center, Float3::new(width, height, 0.0), BOUNDS_COLOR, 0.0);
+        }
+    }
+
+    pub fn Render(&self, entity: &EntityHeader, renderer: &mut Renderer)
+    {
+        renderer.DrawText(
+            &self.Font,
+            self.Text.as_str(),
+            entity.WorldPosition + self.Offset,
+            &self.Style);
+    }
+}
+
+/// Text placed in the scene on its own, in the world or on the UI layer.
+pub struct Label
+{
+    pub Header: EntityHeader,
+    pub Text: TextRenderer
+}
+
+impl Label
+{
+    pub fn Create(name: &str, position: Float3, text: TextRenderer) -> Self
+    {
+        Self
+        {
+            Header: EntityHeader::Create(name, position),
+            Text: text
+        }
+    }
+}
+
+impl Debug for Label {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "Label {}", self.Header.Name)
+    }
+}
+
+impl TEntity for Label
+{
+    fn HasStartBeenCalled(&self) -> bool
+    {
+        self.Header.HasStartBeenCalled()
+    }
+
+    fn ID(&self) -> Uuid
+    {
+        self.Header.ID()
+    }
+
+    unsafe fn Start(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    unsafe fn Update(&mut self, _frame: &GameFrame, api: *mut GameAPI)
+    {
+        self.Text.DrawBounds(&self.Header, &mut (*api).Debug);
+    }
+
+    unsafe fn OnDestroy(&mut self, _api: *mut GameAPI)
+    {
+    }
+
+    fn Render(&mut self, _frame: &GameFrame, renderer: &mut Renderer)
+    {
+        self.Text.Render(&self.Header, renderer);
+    }
+}
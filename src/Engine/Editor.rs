@@ -1,4 +1,5 @@
 mod EditorAssets;
+pub mod dev_overlay;
 
 use glium::Frame;
 use uuid::Uuid;
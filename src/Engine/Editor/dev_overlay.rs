@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Math::Float3;
+use crate::Engine::Rendering::Renderer;
+use crate::Engine::Rendering::post_process::PostProcessStack;
+use crate::Engine::Rendering::debug_draw::{DebugDraw, EDebugSpace, GREEN, RED, WHITE, YELLOW};
+use crate::Engine::SceneManager::SceneManager;
+use crate::Engine::Shader::ShaderModule;
+
+/// Frames kept for the graphs.
+const HISTORY_LENGTH: usize = 100;
+
+/// Frame time the graphs draw a reference line at, 60 fps.
+const TARGET_FRAME_MILLISECONDS: f32 = 1000.0 / 60.0;
+
+/// Top of each graph, in milliseconds and frames per second.
+const FRAME_TIME_GRAPH_MAX: f32 = 50.0;
+const FPS_GRAPH_MAX: f32 = 120.0;
+
+const GRAPH_SIZE: (f32, f32) = (100.0, 20.0);
+const LINE_HEIGHT: f32 = 8.0;
+const MARGIN: f32 = 4.0;
+
+const GRAY: [f32; 4] = [0.6, 0.6, 0.6, 0.6];
+
+/// Runtime developer mode, shown over the game on a key press.
+///
+/// While enabled collider outlines and other debug drawing show, the editor camera
+/// controls are active and the overlay lists frame timing, draw calls, entity counts
+/// and the current scene. Frames are recorded while hidden so the graphs are full when it opens.
+pub struct DevOverlay
+{
+    _isEnabled: bool,
+
+    /// Time between frames, newest last.
+    _frameTimes: VecDeque<f32>,
+
+    /// Time spent updating and drawing inside each frame.
+    _updateTimes: VecDeque<f32>
+}
+
+impl DevOverlay
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            _isEnabled: false,
+            _frameTimes: VecDeque::with_capacity(HISTORY_LENGTH),
+            _updateTimes: VecDeque::with_capacity(HISTORY_LENGTH)
+        }
+    }
+
+    pub fn IsEnabled(&self) -> bool
+    {
+        self._isEnabled
+    }
+
+    /// Debug drawing follows the overlay so outlines only show in developer mode.
+    pub fn SetEnabled(&mut self, isEnabled: bool, debug: &mut DebugDraw)
+    {
+        self._isEnabled = isEnabled;
+        debug.SetEnabled(isEnabled);
+    }
+
+    /// Returns the new state.
+    pub fn Toggle(&mut self, debug: &mut DebugDraw) -> bool
+    {
+        self.SetEnabled(!self._isEnabled, debug);
+        self._isEnabled
+    }
+
+    /// frameMilliseconds - Time since the previous frame.
+    /// updateMilliseconds - Time the engine spent on this frame.
+    pub fn RecordFrame(&mut self, frameMilliseconds: f32, updateMilliseconds: f32)
+    {
+        Self::Push(&mut self._frameTimes, frameMilliseconds);
+        Self::Push(&mut self._updateTimes, updateMilliseconds);
+    }
+
+    /// Average frames per second over the recorded history.
+    pub fn AverageFps(&self) -> f32
+    {
+        let average = Self::Average(&self._frameTimes);
+        if average > 0.0 { 1000.0 / average } else { 0.0 }
+    }
+
+    /// Submits the overlay to debug drawing, anchored to the left of the screen.
+    ///
+    /// renderer - Counts come from the renderer's last draw.
+    /// shaders - The latest shader error is shown until it is fixed.
+    /// postProcess - Passes toggled on with F1 - F5 are listed.
+    pub fn Render(
+        &self,
+        debug: &mut DebugDraw,
+        scene: &SceneManager,
+        renderer: &Renderer,
+        shaders: &ShaderModule,
+        assets: &AssetManager,
+        postProcess: &PostProcessStack,
+        screen: (u32, u32))
+    {
+        if !self._isEnabled
+        {
+            return;
+        }
+
+        let averageFrame = Self::Average(&self._frameTimes);
+        let averageUpdate = Self::Average(&self._updateTimes);
+        let worstFrame = self._frameTimes.iter().cloned().fold(0.0, f32::max);
+
+        let mut lines = vec![
+            format!("SCENE: {}", scene.CurrentSceneName()),
+            format!("FPS: {:.0}  FRAME: {:.1} MS  WORST: {:.1} MS", self.AverageFps(), averageFrame, worstFrame),
+            format!("UPDATE: {:.1} MS", averageUpdate),
+            format!("DRAW CALLS: {}  QUADS: {}  LINES: {}", renderer.DrawCallCount(), renderer.QuadCount(), debug.LineCount()),
+            format!("TEXTURES: {}  UNLOADED ON SCENE LOAD: {}", assets.LoadedTextureCount(), scene.UnloadedTextureCount()),
+        ];
+
+        let enabledPasses: Vec<String> = postProcess
+            .Passes()
+            .iter()
+            .filter(|x| x.IsEnabled)
+            .map(|x| x.Name.to_uppercase())
+            .collect();
+
+        lines.push(format!(
+            "POST PROCESS (F1 - F5): {}",
+            if enabledPasses.is_empty() { String::from("NONE") } else { enabledPasses.join(", ") }));
+
+        for (name, count) in scene.Entities.Counts()
+        {
+            if count > 0
+            {
+                lines.push(format!("{}: {}", name.to_uppercase(), count));
+            }
+        }
+
+        let mut top = screen.1 as f32 - MARGIN - LINE_HEIGHT * 2.0;
+
+        for line in lines
+        {
+            debug.Text(EDebugSpace::Screen, Float3::new(MARGIN, top, 0.0), line.as_str(), WHITE, 0.0);
+            top -= LINE_HEIGHT;
+        }
+
+        // Only the first line of the error fits, the full log is in the console.
+        if let Some(error) = shaders.LastError().and_then(|x| x.lines().next())
+        {
+            debug.Text(EDebugSpace::Screen, Float3::new(MARGIN, top, 0.0), format!("SHADER ERROR: {}", error).as_str(), RED, 0.0);
+        }
+
+        let fps: Vec<f32> = self._frameTimes
+            .iter()
+            .map(|x| if *x > 0.0 { 1000.0 / x } else { 0.0 })
+            .collect();
+
+        let frameTimes: Vec<f32> = self._frameTimes.iter().cloned().collect();
+
+        let graphBottom = MARGIN;
+        self.Graph(debug, "FPS", &fps, FPS_GRAPH_MAX, 1000.0 / TARGET_FRAME_MILLISECONDS, (MARGIN, graphBottom));
+        self.Graph(debug, "MS", &frameTimes, FRAME_TIME_GRAPH_MAX, TARGET_FRAME_MILLISECONDS, (MARGIN, graphBottom + GRAPH_SIZE.1 + LINE_HEIGHT + MARGIN));
+    }
+
+    /// Line graph of the samples inside a box, with a reference line at the target value.
+    fn Graph(&self, debug: &mut DebugDraw, label: &str, samples: &Vec<f32>, max: f32, target: f32, origin: (f32, f32))
+    {
+        let (width, height) = GRAPH_SIZE;
+        let bottomLeft = Float3::new(origin.0, origin.1, 0.0);
+
+        debug.Rect(EDebugSpace::Screen, bottomLeft + Float3::new(width * 0.5, height * 0.5, 0.0), Float3::new(width, height, 0.0), GRAY, 0.0);
+
+        let targetY = origin.1 + (target / max).min(1.0) * height;
+        debug.Line(EDebugSpace::Screen, Float3::new(origin.0, targetY, 0.0), Float3::new(origin.0 + width, targetY, 0.0), YELLOW, 0.0);
+
+        let step = width / (HISTORY_LENGTH - 1) as f32;
+
+        let point = |i: usize, value: f32|
+            {
+                Float3::new(origin.0 + i as f32 * step, origin.1 + (value / max).clamp(0.0, 1.0) * height, 0.0)
+            };
+
+        for i in 1..samples.len()
+        {
+            debug.Line(EDebugSpace::Screen, point(i - 1, samples[i - 1]), point(i, samples[i]), GREEN, 0.0);
+        }
+
+        let current = samples.last().cloned().unwrap_or(0.0);
+        debug.Text(
+            EDebugSpace::Screen,
+            Float3::new(origin.0, origin.1 + height + LINE_HEIGHT, 0.0),
+            format!("{} {:.1}", label, current).as_str(),
+            WHITE,
+            0.0);
+    }
+
+    fn Push(history: &mut VecDeque<f32>, value: f32)
+    {
+        if history.len() == HISTORY_LENGTH
+        {
+            history.pop_front();
+        }
+
+        history.push_back(value);
+    }
+
+    fn Average(history: &VecDeque<f32>) -> f32
+    {
+        if history.is_empty()
+        {
+            return 0.0;
+        }
+
+        history.iter().sum::<f32>() / history.len() as f32
+    }
+}
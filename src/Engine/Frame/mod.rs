@@ -1,33 +1,45 @@
-use cgmath::{num_traits::ToPrimitive, Matrix4, SquareMatrix};
-use chrono::TimeDelta;
-use crate::Engine::Frame::Input::*;
-
-pub mod Input;
-
-/// Frame information that should be passed to game entities.
-pub struct GameFrame
-{
-    pub Input : InputState,
-    pub TimeSinceGameStart: TimeDelta,
-    pub DeltaTime: TimeDelta,
-    pub DeltaTime_Seconds: f32,
-    pub CameraView: Matrix4<f32>,
-    pub CameraPerspective: Matrix4<f32>,
-}
-
-
-impl GameFrame
-{
-    pub fn new(input: InputState, timeSinceGameStart: TimeDelta, deltaTime: TimeDelta, matrix: Matrix4<f32>, scale: Matrix4<f32>) -> Self
-    {
-        Self
-        {
-            Input: input,
-            TimeSinceGameStart: timeSinceGameStart,
-            DeltaTime: deltaTime,
-            DeltaTime_Seconds: deltaTime.num_milliseconds().to_f32().unwrap() / 100.0,
-            CameraView: matrix,
-            CameraPerspective: scale
-        }
-    }
-}
+use cgmath::num_traits::ToPrimitive;
+use chrono::TimeDelta;
+use crate::Engine::Frame::Input::*;
+
+pub mod Input;
+pub mod time;
+
+/// Frame information that should be passed to game entities.
+pub struct GameFrame
+{
+    pub Input : InputState,
+    pub TimeSinceGameStart: TimeDelta,
+    pub DeltaTime: TimeDelta,
+    pub DeltaTime_Seconds: f32,
+
+    /// Paused and scaled time since the game started.
+    pub GameTime: TimeDelta,
+
+    /// Paused and scaled time since the last frame.
+    pub GameDeltaTime: TimeDelta,
+}
+
+
+impl GameFrame
+{
+    pub fn new(input: InputState, timeSinceGameStart: TimeDelta, deltaTime: TimeDelta,
+               gameTime: TimeDelta, gameDeltaTime: TimeDelta) -> Self
+    {
+        Self
+        {
+            Input: input,
+            TimeSinceGameStart: timeSinceGameStart,
+            DeltaTime: deltaTime,
+            DeltaTime_Seconds: deltaTime.num_milliseconds().to_f32().unwrap() / 100.0,
+            GameTime: gameTime,
+            GameDeltaTime: gameDeltaTime
+        }
+    }
+
+    /// Game delta time in milliseconds.
+    pub fn GameDeltaTime_Milliseconds(&self) -> f32
+    {
+        self.GameDeltaTime.num_microseconds().unwrap_or(0) as f32 / 1000.0
+    }
+}
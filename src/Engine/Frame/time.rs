@@ -0,0 +1,54 @@
+use chrono::TimeDelta;
+
+/// Tracks game time separately from real time.
+/// Game time can be paused and scaled, real time always moves forward.
+pub struct GameClock
+{
+    /// Multiplier applied to real time to produce game time.
+    pub TimeScale: f32,
+
+    /// When paused game time does not advance.
+    pub IsPaused: bool,
+
+    _gameTime: TimeDelta,
+    _gameDeltaTime: TimeDelta
+}
+
+impl GameClock
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            TimeScale: 1.0,
+            IsPaused: false,
+            _gameTime: TimeDelta::zero(),
+            _gameDeltaTime: TimeDelta::zero()
+        }
+    }
+
+    /// Advances game time by the provided real time.
+    /// Returns the amount game time moved.
+    pub fn Advance(&mut self, realDeltaTime: TimeDelta) -> TimeDelta
+    {
+        self._gameDeltaTime =
+            if self.IsPaused
+            {
+                TimeDelta::zero()
+            }
+            else
+            {
+                let micros = realDeltaTime.num_microseconds().unwrap_or(0) as f64;
+                TimeDelta::microseconds((micros * self.TimeScale.max(0.0) as f64) as i64)
+            };
+
+        self._gameTime = self._gameTime + self._gameDeltaTime;
+        self._gameDeltaTime
+    }
+
+    /// Total game time elapsed since the clock was created.
+    pub fn GameTime(&self) -> TimeDelta
+    {
+        self._gameTime
+    }
+}
@@ -1,260 +1,343 @@
-#![allow(nonstandard_style)]
-
-use std::sync::Arc;
-use std::sync::Mutex;
-use std::cell::RefCell;
-use std::rc::Rc;
-use std::sync::RwLock;
-use std::time::SystemTime;
-use chrono::{DateTime, Local};
-use glium::{Display, Surface};
-use glium::glutin::surface::WindowSurface;
-use winit::event::KeyEvent;
-use winit::event::MouseButton;
-use winit::event::{ElementState, MouseScrollDelta, TouchPhase};
-
-use crate::Engine::Frame::GameFrame;
-use crate::Engine::Frame::Input::Input;
-use crate::Engine::GameEntity::TEntity;
-use crate::Engine::Components::{self, *};
-use crate::Engine::Math::*;
-use crate::Engine::GameAPI::GameAPI;
-use crate::Engine::SceneBuilder::SceneBuilderFunction;
-use crate::Entities::Entities;
-
-/// The Game Application that is running currently.
-pub struct Game    
-{
-    pub API: GameAPI,
-}
-
-impl Game
-{
-    /// Constructs game and performs any tasks before actual application window opens.
-    pub fn New(sceneBuilderMethod: SceneBuilderFunction) -> Self
-    {
-        Self
-        {
-            API:
-            GameAPI::Create(sceneBuilderMethod)
-        }
-    }
-
-    /// Begins the game loop.
-    pub fn Run(&mut self)
-    {
-        let timeStart = Local::now();
-
-        let event_loop = winit::event_loop::EventLoopBuilder::new()
-            .build()
-            .expect("event loop building");
-
-        let (window, display) =
-            glium::backend::glutin::SimpleWindowBuilder::new()
-                .with_title("Mini Quest Engine Test")
-                .with_inner_size(800, 600)
-                .build(&event_loop);
-
-        // Adds all levels that should be available for loading.
-        self.API.SceneManager.AddScene("Level1", "Scenes/test.lvl");
-        
-        // Build starting scene.
-        self.API.SceneManager.LoadScene("Level1", &display);
-
-        unsafe
-        {
-            Entities::Start(&mut self.API);
-        }
-
-        // Enter frame loop
-        let mut input = Input::New();
-        let mut dateTimeLastFrame = Local::now();
-
-        // TODO: Break this closure up into static functions
-        event_loop.run( |event, window_target|
-        {
-            match event
-            {
-                winit::event::Event::WindowEvent { event, .. } => match event
-                {
-                    winit::event::WindowEvent::KeyboardInput {event, ..} 
-                        => Self::KeyBoardInput(&mut input, event),
-
-                    winit::event::WindowEvent::MouseInput {state, button, ..} 
-                        => Self::MouseInput(&mut input, state, button),
-
-                    winit::event::WindowEvent::MouseWheel {phase, delta, ..} 
-                        => Self::MouseWheel(&mut input, phase, delta),
-
-                    winit::event::WindowEvent::CursorMoved {position,..} =>
-                    {
-                        let _ = &input.SetMousePosition((position.x, position.y));
-                    },
-
-                    winit::event::WindowEvent::CloseRequested =>
-                    {
-                        window_target.exit();
-                    },
-
-
-                    // We now need to render everything in response to a RedrawRequested event due to the animation
-                    winit::event::WindowEvent::RedrawRequested =>
-                        {
-                            Self::Update(
-                                &display,
-                                &mut self.API,
-                                &mut input,
-                                timeStart,
-                                &mut dateTimeLastFrame
-                            );
-                        },
-
-                    // Because glium doesn't know about windows we need to resize the display
-                    // when the window's size has changed.
-                    winit::event::WindowEvent::Resized(window_size) =>
-                        {
-                            display.resize(window_size.into());
-                        },
-
-                    _ => (),
-                },
-                // By requesting a redraw in response to a AboutToWait event we get continuous rendering.
-                // For applications that only change due to user Input you could remove this handler.
-                winit::event::Event::AboutToWait => {
-                    window.request_redraw();
-                },
-                _ => (),
-            }
-        })
-            .unwrap();
-    }
-
-
-
-    /// General Engine Update cycle.
-    pub fn Update(
-        display: &Display<WindowSurface>,
-        api: &mut GameAPI,
-        input: &mut Input,
-        timeStart: DateTime<Local>,
-        dateTimeLastFrame: &mut DateTime<Local>
-    )
-    {
-        println!("Update");
-        let now = SystemTime::now();
-        let mut renderTime: u128 = 0;
-
-        let mut target = display.draw();
-
-        target.clear_color_and_depth((0.1, 0.0, 0.2, 1.0), 1.0);
-
-        api.Audio.Update();
-
-        let timeLastFrame = dateTimeLastFrame.clone();
-
-        let viewMatrix = api.SceneManager.Entities.Camera.ViewMatrix();
-        let perspective= api.SceneManager.Entities.Camera.PerspectiveMatrix();
-
-        let frame =
-            Rc::new(
-                GameFrame::new(
-                    input.GetStateCopy(),
-                    Local::now() - timeStart,
-                    Local::now() - timeLastFrame,
-                    viewMatrix,
-                    perspective
-                )
-            );
-
-        Entities::Update(&frame, api, &mut target);
-        //scene.Entities.PruneDeadEntities();
-
-
-        input.ResetPressedAndReleased();
-        input.SetMouseWheelPixelDelta((0.0, 0.0));
-        input.SetMouseWheelLineOffset((0.0, 0.0));
-
-        *dateTimeLastFrame = Local::now();
-
-        let rnow = SystemTime::now();
-        let _ = target.finish();
-        display.finish();
-        renderTime = renderTime + rnow.elapsed().unwrap().as_millis();
-
-        match now.elapsed()
-        {
-                Ok(elapsed) => 
-                    {
-                        println!("ms:{} fps:{}", elapsed.as_millis(), (1_000_000_000.0 / elapsed.as_nanos() as f32) as u32);
-                        println!("render:{}", renderTime);
-
-                    },
-                _ => {}
-        };
-    }
-
-    pub fn KeyBoardInput(input: &mut Input, event: KeyEvent)
-    {
-        match event.state
-        {
-            ElementState::Pressed =>
-            {
-                &input.Key_Pressed(event.physical_key)
-            },
-
-            ElementState::Released =>
-            {
-                &input.Key_Released(event.physical_key)
-            }
-        };
-    }
-
-    pub fn MouseInput(input: &mut Input, state: ElementState, button: MouseButton)
-    {
-        match state
-        {
-            ElementState::Pressed =>
-                {
-                    let _ = &input.Mouse_Pressed(button);
-                }
-            ElementState::Released =>
-                {
-                    let _ = &input.Mouse_Release(button);
-                }
-        }
-    }
-
-    pub fn MouseWheel(input: &mut Input, phase: TouchPhase, delta: MouseScrollDelta )
-    {
-        match delta
-        {
-            MouseScrollDelta::LineDelta(x, y) =>
-            {
-                input.SetMouseWheelLineOffset((x,y));
-            },
-
-            MouseScrollDelta::PixelDelta(value) =>
-            {
-                input.SetMouseWheelPixelDelta( (value.x, value.y));
-            }
-        };
-
-        match phase
-        {
-            TouchPhase::Started => {}
-            TouchPhase::Moved => {}
-            TouchPhase::Ended =>
-                {
-                    input.SetMouseWheelPixelDelta((0.0, 0.0));
-                    input.SetMouseWheelLineOffset((0.0, 0.0));
-                },
-            TouchPhase::Cancelled =>
-                {
-                    input.SetMouseWheelPixelDelta((0.0, 0.0));
-                    input.SetMouseWheelLineOffset((0.0, 0.0));
-                },
-        };
-    }
-}
-
-
+#![allow(nonstandard_style)]
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::RwLock;
+use std::time::SystemTime;
+use chrono::{DateTime, Local};
+use glium::{Display, Surface};
+use glium::glutin::surface::WindowSurface;
+use winit::event::KeyEvent;
+use winit::keyboard::KeyCode;
+use winit::event::MouseButton;
+use winit::event::{ElementState, MouseScrollDelta, TouchPhase};
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::Rendering::RenderView;
+use crate::Engine::Frame::Input::Input;
+use crate::Engine::Components::{self, *};
+use crate::Engine::Math::*;
+use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::SceneBuilder::SceneBuilderFunction;
+use crate::Entities::Entities;
+
+/// The Game Application that is running currently.
+pub struct Game    
+{
+    pub API: GameAPI,
+}
+
+impl Game
+{
+    /// Constructs game and performs any tasks before actual application window opens.
+    pub fn New(sceneBuilderMethod: SceneBuilderFunction) -> Self
+    {
+        Self
+        {
+            API:
+            GameAPI::Create(sceneBuilderMethod)
+        }
+    }
+
+    /// Begins the game loop.
+    pub fn Run(&mut self)
+    {
+        let timeStart = Local::now();
+
+        let event_loop = winit::event_loop::EventLoopBuilder::new()
+            .build()
+            .expect("event loop building");
+
+        let (window, display) =
+            glium::backend::glutin::SimpleWindowBuilder::new()
+                .with_title("Mini Quest Engine Test")
+                .with_inner_size(960, 540)
+                .build(&event_loop);
+
+        // Adds all levels that should be available for loading.
+        self.API.SceneManager.AddScene("Level1", "Scenes/test.lvl");
+        
+        // Build starting scene.
+        self.API.SceneManager.LoadScene("Level1", &display, &mut self.API.Assets);
+
+        unsafe
+        {
+            Entities::Start(&mut self.API);
+        }
+
+        // Enter frame loop
+        let mut input = Input::New();
+        let mut dateTimeLastFrame = Local::now();
+
+        // TODO: Break this closure up into static functions
+        event_loop.run( |event, window_target|
+        {
+            match event
+            {
+                winit::event::Event::WindowEvent { event, .. } => match event
+                {
+                    winit::event::WindowEvent::KeyboardInput {event, ..} 
+                        => Self::KeyBoardInput(&mut input, event),
+
+                    winit::event::WindowEvent::MouseInput {state, button, ..} 
+                        => Self::MouseInput(&mut input, state, button),
+
+                    winit::event::WindowEvent::MouseWheel {phase, delta, ..} 
+                        => Self::MouseWheel(&mut input, phase, delta),
+
+                    winit::event::WindowEvent::CursorMoved {position,..} =>
+                    {
+                        let _ = &input.SetMousePosition((position.x, position.y));
+                    },
+
+                    winit::event::WindowEvent::CloseRequested =>
+                    {
+                        window_target.exit();
+                    },
+
+
+                    // We now need to render everything in response to a RedrawRequested event due to the animation
+                    winit::event::WindowEvent::RedrawRequested =>
+                        {
+                            Self::Update(
+                                &display,
+                                &mut self.API,
+                                &mut input,
+                                timeStart,
+                                &mut dateTimeLastFrame
+                            );
+                        },
+
+                    // Because glium doesn't know about windows we need to resize the display
+                    // when the window's size has changed.
+                    winit::event::WindowEvent::Resized(window_size) =>
+                        {
+                            display.resize(window_size.into());
+                        },
+
+                    _ => (),
+                },
+                // By requesting a redraw in response to a AboutToWait event we get continuous rendering.
+                // For applications that only change due to user Input you could remove this handler.
+                winit::event::Event::AboutToWait => {
+                    window.request_redraw();
+                },
+                _ => (),
+            }
+        })
+            .unwrap();
+    }
+
+
+
+    /// General Engine Update cycle.
+    pub fn Update(
+        display: &Display<WindowSurface>,
+        api: &mut GameAPI,
+        input: &mut Input,
+        timeStart: DateTime<Local>,
+        dateTimeLastFrame: &mut DateTime<Local>
+    )
+    {
+        let now = SystemTime::now();
+
+        let mut target = display.draw();
+        api.Screen.SetWindowSize(target.get_dimensions());
+
+        target.clear_color_and_depth((0.1, 0.0, 0.2, 1.0), 1.0);
+
+        api.Audio.Update();
+
+        // Edited shader files are swapped in before anything draws.
+        api.Shader.Update(display);
+
+        let timeLastFrame = dateTimeLastFrame.clone();
+        let deltaTime = Local::now() - timeLastFrame;
+        let gameDeltaTime = api.Time.Advance(deltaTime);
+
+        let frame =
+            Rc::new(
+                GameFrame::new(
+                    input.GetStateCopy(),
+                    Local::now() - timeStart,
+                    deltaTime,
+                    api.Time.GameTime(),
+                    gameDeltaTime
+                )
+            );
+
+        Entities::Update(&frame, api);
+        Self::PostProcessInput(&frame, api);
+        Self::DevOverlayInput(&frame, api);
+
+        let screen = (api.Screen.Width, api.Screen.Height);
+        api.DevOverlay.Render(&mut api.Debug, &api.SceneManager, &api.Renderer, &api.Shader, &api.Assets, &api.PostProcess, screen);
+
+        api.Debug.Prepare(display, &mut api.Assets);
+
+        // Scene is drawn at the virtual resolution, then scaled into the window.
+        api.Screen.Prepare(display);
+
+        // Each camera draws its own viewport, views are taken after every entity has updated.
+        let views: Vec<RenderView> = api.SceneManager.Entities
+            .CamerasByPriority()
+            .iter()
+            .map(|x| x.RenderView(screen))
+            .collect();
+
+        {
+            let mut sceneTarget = api.Screen.SceneTarget(display);
+            let c = api.Screen.LetterboxColor;
+            sceneTarget.clear_color(c.0, c.1, c.2, c.3);
+
+            api.Renderer.Draw(
+                display,
+                &mut sceneTarget,
+                &mut api.Shader,
+                &frame,
+                &views);
+        }
+
+        api.PostProcess.Prepare(display, &mut api.Shader, (api.Screen.Width, api.Screen.Height));
+
+        if api.PostProcess.IsActive(&api.Shader)
+        {
+            let c = api.Screen.LetterboxColor;
+            target.clear_color(c.0, c.1, c.2, c.3);
+
+            let viewport = api.Screen.Viewport(target.get_dimensions());
+
+            api.PostProcess.Apply(
+                display,
+                api.Screen.SceneTexture(),
+                &mut target,
+                viewport,
+                &api.Shader,
+                &frame);
+        }
+        else
+        {
+            api.Screen.Present(&mut target);
+        }
+
+        // Debug shapes go over the finished image, so post processing never warps them.
+        let mainView = api.SceneManager.Entities.MainCamera().RenderView(screen);
+        let viewport = api.Screen.Viewport(target.get_dimensions());
+        api.Debug.Render(display, &mut target, &mut api.Shader, &views, &mainView, viewport, screen, &frame);
+        //scene.Entities.PruneDeadEntities();
+
+
+        input.ResetPressedAndReleased();
+        input.SetMouseWheelPixelDelta((0.0, 0.0));
+        input.SetMouseWheelLineOffset((0.0, 0.0));
+
+        *dateTimeLastFrame = Local::now();
+
+        let _ = target.finish();
+        display.finish();
+
+        // Shown by the developer overlay from the next frame on.
+        let updateTime = now.elapsed().map_or(0.0, |x| x.as_secs_f32() * 1000.0);
+        let frameTime = deltaTime.num_microseconds().unwrap_or(0) as f32 / 1000.0;
+        api.DevOverlay.RecordFrame(frameTime, updateTime);
+    }
+
+    /// F1 - F5 toggle the first five post process passes.
+    fn PostProcessInput(frame: &GameFrame, api: &mut GameAPI)
+    {
+        let keys = [KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5];
+
+        for (i, key) in keys.iter().enumerate()
+        {
+            if !frame.Input.IsKeyPressed(*key)
+            {
+                continue;
+            }
+
+            let name = match api.PostProcess.Passes().get(i)
+            {
+                Some(x) => x.Name.clone(),
+                None => continue
+            };
+
+            // The dev overlay lists which passes are on.
+            api.PostProcess.TogglePass(name.as_str());
+        }
+    }
+
+    /// F9 toggles developer mode and its overlay.
+    fn DevOverlayInput(frame: &GameFrame, api: &mut GameAPI)
+    {
+        if frame.Input.IsKeyPressed(KeyCode::F9)
+        {
+            api.DevOverlay.Toggle(&mut api.Debug);
+        }
+    }
+
+    pub fn KeyBoardInput(input: &mut Input, event: KeyEvent)
+    {
+        match event.state
+        {
+            ElementState::Pressed =>
+            {
+                &input.Key_Pressed(event.physical_key)
+            },
+
+            ElementState::Released =>
+            {
+                &input.Key_Released(event.physical_key)
+            }
+        };
+    }
+
+    pub fn MouseInput(input: &mut Input, state: ElementState, button: MouseButton)
+    {
+        match state
+        {
+            ElementState::Pressed =>
+                {
+                    let _ = &input.Mouse_Pressed(button);
+                }
+            ElementState::Released =>
+                {
+                    let _ = &input.Mouse_Release(button);
+                }
+        }
+    }
+
+    pub fn MouseWheel(input: &mut Input, phase: TouchPhase, delta: MouseScrollDelta )
+    {
+        match delta
+        {
+            MouseScrollDelta::LineDelta(x, y) =>
+            {
+                input.SetMouseWheelLineOffset((x,y));
+            },
+
+            MouseScrollDelta::PixelDelta(value) =>
+            {
+                input.SetMouseWheelPixelDelta( (value.x, value.y));
+            }
+        };
+
+        match phase
+        {
+            TouchPhase::Started => {}
+            TouchPhase::Moved => {}
+            TouchPhase::Ended =>
+                {
+                    input.SetMouseWheelPixelDelta((0.0, 0.0));
+                    input.SetMouseWheelLineOffset((0.0, 0.0));
+                },
+            TouchPhase::Cancelled =>
+                {
+                    input.SetMouseWheelPixelDelta((0.0, 0.0));
+                    input.SetMouseWheelLineOffset((0.0, 0.0));
+                },
+        };
+    }
+}
+
+
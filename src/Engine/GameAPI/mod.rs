@@ -1,43 +1,64 @@
-use crate::Engine::SceneManager::SceneManager;
-use crate::Engine::GameState::GameState;
-use crate::Engine::MetaInfo::MetaInfo;
-use crate::Engine::Audio::AudioModule;
-use crate::Engine::Collision::CollisionModule;
-use crate::Engine::SceneBuilder::SceneBuilderFunction;
-use crate::Engine::Shader::ShaderModule;
-
-/// # Description
-///     General utilities that should be exposed to game entities.
-///     This allows entities to access and manipulate game data, 
-///     meta information, game entities, and scenes.
-pub struct GameAPI
-{
-    pub SceneManager: SceneManager,
-    pub GameState: GameState,
-    pub MetaInfo: MetaInfo,
-
-    pub Audio: AudioModule,
-    pub Collision: CollisionModule,
-    pub Shader: ShaderModule
-}
-
-impl GameAPI
-{
-    pub fn Create(sceneBuilderMethod: SceneBuilderFunction) -> Self
-    {
-        Self
-        {
-            SceneManager: SceneManager::Create(sceneBuilderMethod),
-            GameState: GameState::Create(),
-            MetaInfo: MetaInfo::Create(),
-            Audio: AudioModule::Create(),
-            Collision: CollisionModule::Create(),
-            Shader: ShaderModule::Create()
-        }
-    }
-}
-
-unsafe impl Send for GameAPI{}
-
-
-
+use crate::Engine::SceneManager::SceneManager;
+use crate::Engine::GameState::GameState;
+use crate::Engine::MetaInfo::MetaInfo;
+use crate::Engine::Audio::AudioModule;
+use crate::Engine::Collision::CollisionModule;
+use crate::Engine::SceneBuilder::SceneBuilderFunction;
+use crate::Engine::Shader::ShaderModule;
+use crate::Engine::Frame::time::GameClock;
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Rendering::Renderer;
+use crate::Engine::Rendering::debug_draw::DebugDraw;
+use crate::Engine::Editor::dev_overlay::DevOverlay;
+use crate::Engine::Rendering::post_process::{PostProcessStack, DEFAULT_CHAIN};
+use crate::Engine::Rendering::virtual_screen::{VirtualScreen, DEFAULT_VIRTUAL_RESOLUTION};
+
+/// # Description
+///     General utilities that should be exposed to game entities.
+///     This allows entities to access and manipulate game data, 
+///     meta information, game entities, and scenes.
+pub struct GameAPI
+{
+    pub SceneManager: SceneManager,
+    pub GameState: GameState,
+    pub MetaInfo: MetaInfo,
+
+    pub Audio: AudioModule,
+    pub Collision: CollisionModule,
+    pub Shader: ShaderModule,
+    pub Assets: AssetManager,
+    pub Renderer: Renderer,
+    pub PostProcess: PostProcessStack,
+    pub Screen: VirtualScreen,
+    pub Debug: DebugDraw,
+    pub DevOverlay: DevOverlay,
+    pub Time: GameClock
+}
+
+impl GameAPI
+{
+    pub fn Create(sceneBuilderMethod: SceneBuilderFunction) -> Self
+    {
+        Self
+        {
+            SceneManager: SceneManager::Create(sceneBuilderMethod),
+            GameState: GameState::Create(),
+            MetaInfo: MetaInfo::Create(),
+            Audio: AudioModule::Create(),
+            Collision: CollisionModule::Create(),
+            Shader: ShaderModule::Create(),
+            Assets: AssetManager::Create(),
+            Renderer: Renderer::Create(),
+            PostProcess: PostProcessStack::FromFile(DEFAULT_CHAIN),
+            Screen: VirtualScreen::Create(DEFAULT_VIRTUAL_RESOLUTION.0, DEFAULT_VIRTUAL_RESOLUTION.1),
+            Debug: DebugDraw::Create(),
+            DevOverlay: DevOverlay::Create(),
+            Time: GameClock::Create()
+        }
+    }
+}
+
+unsafe impl Send for GameAPI{}
+
+
+
@@ -1,18 +1,18 @@
-
-/// Contains game state data. 
-/// Unlike meta game data, this save data doesn't have to be loaded immediatelly
-/// and you can select which save to load. 
-pub struct GameState
-{
-}
-
-impl GameState
-{
-    pub fn Create() -> Self
-    {
-        Self
-        {
-        }
-    }
-}
-
+
+/// Contains game state data. 
+/// Unlike meta game data, this save data doesn't have to be loaded immediatelly
+/// and you can select which save to load. 
+pub struct GameState
+{
+}
+
+impl GameState
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+        }
+    }
+}
+
@@ -0,0 +1,417 @@
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+use cgmath::Vector4;
+use glium::{Display, Rect, Surface, VertexBuffer};
+use glium::glutin::surface::WindowSurface;
+use glium::index::{NoIndices, PrimitiveType};
+
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, RenderView, Renderer};
+use crate::Engine::Rendering::post_process::ShaderPath;
+use crate::Engine::Rendering::text::{Font, TextStyle};
+use crate::Engine::Shader::ShaderModule;
+
+const DEBUG_VERTEX: &str = "V_Debug.shader";
+const DEBUG_FRAGMENT: &str = "F_Debug.shader";
+
+/// Font debug text is drawn with.
+const DEBUG_FONT: &str = "Assets/Fonts/pixel.fnt";
+const DEBUG_FONT_SIZE: f32 = 6.0;
+
+/// Segments used to approximate a full circle.
+const CIRCLE_SEGMENTS: usize = 24;
+
+pub const RED: [f32; 4] = [1.0, 0.2, 0.2, 1.0];
+pub const GREEN: [f32; 4] = [0.2, 1.0, 0.3, 1.0];
+pub const BLUE: [f32; 4] = [0.3, 0.5, 1.0, 1.0];
+pub const YELLOW: [f32; 4] = [1.0, 0.9, 0.2, 1.0];
+pub const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+/// Which coordinates a debug shape is given in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EDebugSpace
+{
+    /// World units, seen through the main camera.
+    World,
+
+    /// Virtual screen pixels, origin at the bottom-left.
+    Screen
+}
+
+#[derive(Copy, Clone)]
+struct DebugVertex
+{
+    position: [f32; 2],
+    color: [f32; 4]
+}
+
+implement_vertex!(DebugVertex, position, color);
+
+struct DebugLine
+{
+    From: Float3,
+    To: Float3,
+    Color: [f32; 4],
+    Space: EDebugSpace,
+
+    /// Seconds left to draw, shapes with none left are drawn once more then dropped.
+    Remaining: f32
+}
+
+struct DebugText
+{
+    Text: String,
+    Position: Float3,
+    Color: [f32; 4],
+    Space: EDebugSpace,
+    Remaining: f32
+}
+
+/// Immediate mode lines, shapes and text for visualizing game state.
+///
+/// Anything can call it at any point of the frame, shapes are kept for their duration,
+/// or a single frame with a duration of 0. Everything is drawn into the window after
+/// post processing so it stays readable, every line with one draw call and text through
+/// a renderer of its own. Calls are ignored while disabled.
+pub struct DebugDraw
+{
+    _isEnabled: bool,
+
+    _lines: Vec<DebugLine>,
+    _texts: Vec<DebugText>,
+
+    _font: Option<Arc<Font>>,
+    _vertexBuffer: Option<VertexBuffer<DebugVertex>>,
+
+    /// Draws the text, kept apart so the scene's draw counts only include the scene.
+    _renderer: Renderer,
+
+    /// Lines drawn by the last Render.
+    _lineCount: usize
+}
+
+impl DebugDraw
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            _isEnabled: false,
+            _lines: Vec::new(),
+            _texts: Vec::new(),
+            _font: None,
+            _vertexBuffer: None,
+            _renderer: Renderer::Create(),
+            _lineCount: 0
+        }
+    }
+
+    /// Disabling drops everything still waiting to be drawn.
+    pub fn SetEnabled(&mut self, isEnabled: bool)
+    {
+        self._isEnabled = isEnabled;
+
+        if !isEnabled
+        {
+            self.Clear();
+        }
+    }
+
+    pub fn Clear(&mut self)
+    {
+        self._lines.clear();
+        self._texts.clear();
+    }
+
+    pub fn LineCount(&self) -> usize
+    {
+        self._lineCount
+    }
+
+    pub fn Line(&mut self, space: EDebugSpace, from: Float3, to: Float3, color: [f32; 4], duration: f32)
+    {
+        if !self._isEnabled
+        {
+            return;
+        }
+
+        self._lines.push(
+            DebugLine
+            {
+                From: from,
+                To: to,
+                Color: color,
+                Space: space,
+                Remaining: duration
+            });
+    }
+
+    /// Outline of an axis aligned rect.
+    pub fn Rect(&mut self, space: EDebugSpace, center: Float3, size: Float3, color: [f32; 4], duration: f32)
+    {
+        let halfWidth = size.x() * 0.5;
+        let halfHeight = size.y() * 0.5;
+
+        let corners =
+        [
+            center + Float3::new(-halfWidth, -halfHeight, 0.0),
+            center + Float3::new( halfWidth, -halfHeight, 0.0),
+            center + Float3::new( halfWidth,  halfHeight, 0.0),
+            center + Float3::new(-halfWidth,  halfHeight, 0.0),
+        ];
+
+        for i in 0..corners.len()
+        {
+            self.Line(space, corners[i], corners[(i + 1) % corners.len()], color, duration);
+        }
+    }
+
+    pub fn Circle(&mut self, space: EDebugSpace, center: Float3, radius: f32, color: [f32; 4], duration: f32)
+    {
+        let point = |i: usize|
+            {
+                let angle = i as f32 / CIRCLE_SEGMENTS as f32 * TAU;
+                center + Float3::new(angle.cos() * radius, angle.sin() * radius, 0.0)
+            };
+
+        for i in 0..CIRCLE_SEGMENTS
+        {
+            self.Line(space, point(i), point(i + 1), color, duration);
+        }
+    }
+
+    /// Line with a head at the end, i.e. for velocities and directions.
+    pub fn Arrow(&mut self, space: EDebugSpace, from: Float3, to: Float3, color: [f32; 4], duration: f32)
+    {
+        self.Line(space, from, to, color, duration);
+
+        let dx = to.x() - from.x();
+        let dy = to.y() - from.y();
+        let length = (dx * dx + dy * dy).sqrt();
+
+        if length <= f32::EPSILON
+        {
+            return;
+        }
+
+        // Head is a quarter of the arrow, swept back 30 degrees either side.
+        let headLength = length * 0.25;
+        let angle = dy.atan2(dx);
+
+        for side in [-1.0f32, 1.0]
+        {
+            let headAngle = angle + std::f32::consts::PI + side * 30f32.to_radians();
+            let end = to + Float3::new(headAngle.cos() * headLength, headAngle.sin() * headLength, 0.0);
+            self.Line(space, to, end, color, duration);
+        }
+    }
+
+    /// Text with its top-left at the position.
+    pub fn Text(&mut self, space: EDebugSpace, position: Float3, text: &str, color: [f32; 4], duration: f32)
+    {
+        if !self._isEnabled
+        {
+            return;
+        }
+
+        self._texts.push(
+            DebugText
+            {
+                Text: String::from(text),
+                Position: position,
+                Color: color,
+                Space: space,
+                Remaining: duration
+            });
+    }
+
+    /// Loads the debug font once it is first needed.
+    pub fn Prepare(&mut self, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        if self._font.is_none() && !self._texts.is_empty()
+        {
+            self._font = Some(assets.Font(DEBUG_FONT, DEBUG_FONT_SIZE, false, display));
+        }
+    }
+
+    /// Queues debug text on the debug renderer.
+    fn SubmitText(&mut self)
+    {
+        let font = match &self._font
+        {
+            Some(x) => x,
+            None => return
+        };
+
+        for text in self._texts.iter()
+        {
+            let mut style = TextStyle::Create();
+            style.Color = text.Color;
+            style.OrderInLayer = i32::MAX;
+
+            // Screen space is in pixels, the UI camera shows pixels per unit.
+            let position = match text.Space
+            {
+                EDebugSpace::World =>
+                    {
+                        style.Layer = ESortingLayer::Foreground;
+                        text.Position
+                    }
+                EDebugSpace::Screen => Float3::scale_value(text.Position, 1.0 / PIXELS_PER_UNIT)
+            };
+
+            self._renderer.DrawText(font, text.Text.as_str(), position, &style);
+        }
+    }
+
+    /// Draws every line in one call and the text over the window, then drops expired shapes.
+    ///
+    /// views - Camera views of the scene, text is drawn through them like the scene is.
+    /// mainView - World lines are projected through this camera.
+    /// viewport - Letterboxed area of the window the scene was presented to.
+    /// screen - Size of the scene in virtual pixels.
+    pub fn Render<S: Surface>(
+        &mut self,
+        display: &Display<WindowSurface>,
+        target: &mut S,
+        shaders: &mut ShaderModule,
+        views: &[RenderView],
+        mainView: &RenderView,
+        viewport: Rect,
+        screen: (u32, u32),
+        frame: &GameFrame)
+    {
+        self._lineCount = 0;
+
+        // Virtual pixels are whole window pixels, see VirtualScreen::Scale.
+        let scale = (viewport.width / screen.0.max(1)).max(1);
+
+        if !self._lines.is_empty()
+        {
+            let vertices = self.Vertices(mainView, screen);
+            self.Draw(display, target, shaders, &vertices, viewport, scale);
+            self._lineCount = self._lines.len();
+        }
+
+        if !self._texts.is_empty()
+        {
+            self.SubmitText();
+
+            let windowViews: Vec<RenderView> = views
+                .iter()
+                .map(|x|
+                    RenderView
+                    {
+                        View: x.View,
+                        Projection: x.Projection,
+                        Viewport: Rect
+                        {
+                            left: viewport.left + x.Viewport.left * scale,
+                            bottom: viewport.bottom + x.Viewport.bottom * scale,
+                            width: x.Viewport.width * scale,
+                            height: x.Viewport.height * scale
+                        },
+                        LayerMask: x.LayerMask,
+                        Position: x.Position,
+                        ClearColor: None
+                    })
+                .collect();
+
+            self._renderer.Draw(display, target, shaders, frame, &windowViews);
+        }
+
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+
+        self._lines.retain_mut(|x| { x.Remaining -= deltaSeconds; x.Remaining > 0.0 });
+        self._texts.retain_mut(|x| { x.Remaining -= deltaSeconds; x.Remaining > 0.0 });
+    }
+
+    /// Every line end in clip space of the whole scene target.
+    fn Vertices(&self, mainView: &RenderView, screen: (u32, u32)) -> Vec<DebugVertex>
+    {
+        let viewProjection = mainView.Projection * mainView.View;
+        let viewport = mainView.Viewport;
+
+        let width = screen.0.max(1) as f32;
+        let height = screen.1.max(1) as f32;
+
+        // Both spaces end up in screen pixels so everything shares one draw.
+        let toClip = |point: Float3, space: EDebugSpace| -> [f32; 2]
+            {
+                let pixel = match space
+                {
+                    EDebugSpace::Screen => (point.x(), point.y()),
+                    EDebugSpace::World =>
+                        {
+                            let clip = viewProjection * Vector4::new(point.x(), point.y(), point.z(), 1.0);
+                            let w = if clip.w.abs() > f32::EPSILON { clip.w } else { 1.0 };
+
+                            (
+                                viewport.left as f32 + (clip.x / w + 1.0) * 0.5 * viewport.width as f32,
+                                viewport.bottom as f32 + (clip.y / w + 1.0) * 0.5 * viewport.height as f32
+                            )
+                        }
+                };
+
+                [pixel.0 / width * 2.0 - 1.0, pixel.1 / height * 2.0 - 1.0]
+            };
+
+        self._lines
+            .iter()
+            .flat_map(|x|
+                [
+                    DebugVertex { position: toClip(x.From, x.Space), color: x.Color },
+                    DebugVertex { position: toClip(x.To, x.Space), color: x.Color }
+                ])
+            .collect()
+    }
+
+    fn Draw<S: Surface>(
+        &mut self,
+        display: &Display<WindowSurface>,
+        target: &mut S,
+        shaders: &mut ShaderModule,
+        vertices: &Vec<DebugVertex>,
+        viewport: Rect,
+        scale: u32)
+    {
+        let program = match shaders.Program(display, ShaderPath(DEBUG_VERTEX).as_str(), ShaderPath(DEBUG_FRAGMENT).as_str())
+        {
+            Some(x) => x,
+            None => return
+        };
+
+        let needsBuffer = match &self._vertexBuffer
+        {
+            Some(x) => x.len() < vertices.len(),
+            None => true
+        };
+
+        if needsBuffer
+        {
+            self._vertexBuffer = Some(VertexBuffer::empty_dynamic(display, vertices.len().next_power_of_two()).unwrap());
+        }
+
+        let buffer = self._vertexBuffer.as_ref().unwrap().slice(0 .. vertices.len()).unwrap();
+        buffer.write(vertices);
+
+        let params = glium::DrawParameters
+        {
+            blend: glium::Blend::alpha_blending(),
+            line_width: Some(scale as f32),
+            viewport: Some(viewport),
+            .. Default::default()
+        };
+
+        target.draw(
+            buffer,
+            NoIndices(PrimitiveType::LinesList),
+            program,
+            &glium::uniforms::EmptyUniforms,
+            &params).unwrap();
+    }
+}
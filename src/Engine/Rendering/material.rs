@@ -0,0 +1,503 @@
+use std::sync::Arc;
+
+use glium::{Display, Program, Texture2d};
+use glium::glutin::surface::WindowSurface;
+use glium::uniforms::{SamplerBehavior, UniformType, UniformValue, Uniforms};
+
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::Rendering::LIT_DEFINE;
+use crate::Engine::SceneBuilder::PROPERTY_SEPARATOR;
+use crate::Engine::Shader::{ShaderModule, DEFAULT_FRAGMENT};
+
+/// Color parameter multiplied into the sprite, applied per vertex rather than as a uniform.
+pub const TINT_PARAMETER: &str = "tint";
+
+/// Value of a named material parameter, passed to the shader as a uniform of the same name.
+#[derive(Clone)]
+pub enum EMaterialParameter
+{
+    Float(f32),
+    Vector2([f32; 2]),
+    Vector3([f32; 3]),
+    Color([f32; 4]),
+    Texture(Arc<Texture2d>)
+}
+
+impl EMaterialParameter
+{
+    /// Parses a parameter from a material or scene file.
+    /// One to four comma separated numbers are a float, vector or color, anything else is a texture path.
+    pub fn Parse(value: &str, display: &Display<WindowSurface>, assets: &mut AssetManager) -> Self
+    {
+        let numbers: Result<Vec<f32>, _> = value
+            .split(',')
+            .map(|x| x.trim().parse::<f32>())
+            .collect();
+
+        match numbers.as_deref()
+        {
+            Ok([x]) => Self::Float(*x),
+            Ok([x, y]) => Self::Vector2([*x, *y]),
+            Ok([x, y, z]) => Self::Vector3([*x, *y, *z]),
+            Ok([r, g, b, a]) => Self::Color([*r, *g, *b, *a]),
+            Ok(_) => panic!("Material parameter {} has too many values", value),
+            Err(_) => Self::Texture(assets.Texture(value.trim(), display))
+        }
+    }
+
+    /// Blends between two values of the same kind, textures switch once t reaches 1.
+    pub fn Lerp(&self, to: &Self, t: f32) -> Self
+    {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+
+        match (self, to)
+        {
+            (Self::Float(a), Self::Float(b)) => Self::Float(mix(*a, *b)),
+            (Self::Vector2(a), Self::Vector2(b)) => Self::Vector2([mix(a[0], b[0]), mix(a[1], b[1])]),
+            (Self::Vector3(a), Self::Vector3(b)) => Self::Vector3([mix(a[0], b[0]), mix(a[1], b[1]), mix(a[2], b[2])]),
+            (Self::Color(a), Self::Color(b)) => Self::Color([mix(a[0], b[0]), mix(a[1], b[1]), mix(a[2], b[2]), mix(a[3], b[3])]),
+            _ => if t >= 1.0 { to.clone() } else { self.clone() }
+        }
+    }
+
+    fn AsUniform(&self, sampler: SamplerBehavior) -> UniformValue<'_>
+    {
+        match self
+        {
+            Self::Float(x) => UniformValue::Float(*x),
+            Self::Vector2(x) => UniformValue::Vec2(*x),
+            Self::Vector3(x) => UniformValue::Vec3(*x),
+            Self::Color(x) => UniformValue::Vec4(*x),
+            Self::Texture(x) => UniformValue::Texture2d(x.as_ref(), Some(sampler))
+        }
+    }
+}
+
+impl PartialEq for EMaterialParameter
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        match (self, other)
+        {
+            (Self::Float(a), Self::Float(b)) => a == b,
+            (Self::Vector2(a), Self::Vector2(b)) => a == b,
+            (Self::Vector3(a), Self::Vector3(b)) => a == b,
+            (Self::Color(a), Self::Color(b)) => a == b,
+            (Self::Texture(a), Self::Texture(b)) => Arc::ptr_eq(a, b),
+            _ => false
+        }
+    }
+}
+
+/// Named parameters of a material, kept sorted by name so equal sets compare equal.
+#[derive(Clone, Default, PartialEq)]
+pub struct MaterialParameters
+{
+    _values: Vec<(String, EMaterialParameter)>
+}
+
+impl MaterialParameters
+{
+    pub fn Get(&self, name: &str) -> Option<&EMaterialParameter>
+    {
+        self._values
+            .binary_search_by(|x| x.0.as_str().cmp(name))
+            .ok()
+            .map(|i| &self._values[i].1)
+    }
+
+    pub fn Set(&mut self, name: &str, value: EMaterialParameter)
+    {
+        match self._values.binary_search_by(|x| x.0.as_str().cmp(name))
+        {
+            Ok(i) => self._values[i].1 = value,
+            Err(i) => self._values.insert(i, (String::from(name), value))
+        }
+    }
+
+    pub fn Remove(&mut self, name: &str)
+    {
+        if let Ok(i) = self._values.binary_search_by(|x| x.0.as_str().cmp(name))
+        {
+            self._values.remove(i);
+        }
+    }
+
+    pub fn Iter(&self) -> impl Iterator<Item = &(String, EMaterialParameter)>
+    {
+        self._values.iter()
+    }
+
+    pub fn IsEmpty(&self) -> bool
+    {
+        self._values.is_empty()
+    }
+}
+
+/// A parameter blending towards a value over time.
+#[derive(Clone)]
+struct ParameterAnimation
+{
+    Name: String,
+    From: EMaterialParameter,
+    To: EMaterialParameter,
+    Duration: f32,
+    Elapsed: f32
+}
+
+/// A shader paired with the values of its parameters.
+///
+/// Materials are loaded from .mat files and shared through the AssetManager, a sprite
+/// copies its material only when it overrides or animates a parameter.
+/// Parameters are copied on write, sprites that never change theirs still batch together.
+///
+/// File Structure:
+///     Shader | [fragment shader path]
+///     Define | [name or name=value]
+///     Parameter | [uniform name] | [value, see EMaterialParameter::Parse]
+#[derive(Clone)]
+pub struct Material
+{
+    /// Fragment shader variant, defines included.
+    _shader: String,
+
+    /// The same variant with LIT_DEFINE added, resolved whenever the shader changes.
+    _litShader: String,
+
+    _parameters: Arc<MaterialParameters>,
+    _animations: Vec<ParameterAnimation>
+}
+
+impl Material
+{
+    pub fn Create(shader: &str) -> Self
+    {
+        let mut material = Self
+        {
+            _shader: String::new(),
+            _litShader: String::new(),
+            _parameters: Arc::new(MaterialParameters::default()),
+            _animations: Vec::new()
+        };
+
+        material.SetVariant(ShaderModule::Variant(shader, &[]));
+        material
+    }
+
+    /// Material drawing with the default sprite shader and no parameters.
+    pub fn Default() -> Self
+    {
+        Self::Create(DEFAULT_FRAGMENT)
+    }
+
+    /// Builds a material from the contents of a .mat file, path is only used for errors.
+    pub fn Parse(path: &str, contents: &str, display: &Display<WindowSurface>, assets: &mut AssetManager) -> Self
+    {
+        let mut material = Self::Default();
+
+        let entries = contents
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty() && !x.starts_with("//"));
+
+        for entry in entries
+        {
+            let tokens: Vec<&str> = entry
+                .split(PROPERTY_SEPARATOR)
+                .map(|x| x.trim())
+                .collect();
+
+            if tokens.len() < 2 || (tokens[0] == "Parameter" && tokens.len() < 3)
+            {
+                panic!("{}: {} is missing properties", path, tokens[0]);
+            }
+
+            match tokens[0]
+            {
+                "Shader" => material.SetShader(tokens[1]),
+                "Define" => material.SetDefine(tokens[1], true),
+                "Parameter" => material.Set(tokens[1], EMaterialParameter::Parse(tokens[2], display, assets)),
+                x => panic!("{}: unknown material entry {}", path, x)
+            }
+        }
+
+        material
+    }
+
+    /// Fragment shader variant the material draws with.
+    pub fn Shader(&self) -> &str
+    {
+        self._shader.as_str()
+    }
+
+    /// Variant a sprite draws with, lit sprites add LIT_DEFINE.
+    pub fn DrawShader(&self, isLit: bool) -> &str
+    {
+        if isLit
+        {
+            self._litShader.as_str()
+        }
+        else
+        {
+            self._shader.as_str()
+        }
+    }
+
+    /// Changes the shader, keeping the defines already enabled.
+    pub fn SetShader(&mut self, shader: &str)
+    {
+        let defines = ShaderModule::SplitVariant(&self._shader).1;
+        self.SetVariant(ShaderModule::Variant(shader, &defines.iter().map(|x| x.as_str()).collect::<Vec<&str>>()));
+    }
+
+    /// Enables or disables a define of the shader, each set of defines compiles its own program.
+    pub fn SetDefine(&mut self, define: &str, isEnabled: bool)
+    {
+        let (file, mut defines) = ShaderModule::SplitVariant(&self._shader);
+        let name = define.split('=').next().unwrap_or("").trim();
+
+        defines.retain(|x| x.split('=').next().unwrap_or("").trim() != name);
+
+        if isEnabled
+        {
+            defines.push(String::from(define.trim()));
+        }
+
+        self.SetVariant(ShaderModule::Variant(&file, &defines.iter().map(|x| x.as_str()).collect::<Vec<&str>>()));
+    }
+
+    /// Stores a resolved variant name along with its lit version.
+    fn SetVariant(&mut self, shader: String)
+    {
+        self._litShader = ShaderModule::Variant(&shader, &[LIT_DEFINE]);
+        self._shader = shader;
+    }
+
+    pub fn HasDefine(&self, define: &str) -> bool
+    {
+        ShaderModule::SplitVariant(&self._shader).1
+            .iter()
+            .any(|x| x.split('=').next().unwrap_or("") == define)
+    }
+
+    pub fn Parameters(&self) -> &Arc<MaterialParameters>
+    {
+        &self._parameters
+    }
+
+    pub fn Get(&self, name: &str) -> Option<&EMaterialParameter>
+    {
+        self._parameters.Get(name)
+    }
+
+    /// Sets a parameter, stopping any animation of it.
+    pub fn Set(&mut self, name: &str, value: EMaterialParameter)
+    {
+        self.StopAnimation(name);
+        self.Write(name, value);
+    }
+
+    pub fn SetFloat(&mut self, name: &str, value: f32)
+    {
+        self.Set(name, EMaterialParameter::Float(value));
+    }
+
+    pub fn SetVector2(&mut self, name: &str, value: [f32; 2])
+    {
+        self.Set(name, EMaterialParameter::Vector2(value));
+    }
+
+    pub fn SetVector3(&mut self, name: &str, value: [f32; 3])
+    {
+        self.Set(name, EMaterialParameter::Vector3(value));
+    }
+
+    pub fn SetColor(&mut self, name: &str, value: [f32; 4])
+    {
+        self.Set(name, EMaterialParameter::Color(value));
+    }
+
+    pub fn SetTexture(&mut self, name: &str, value: Arc<Texture2d>)
+    {
+        self.Set(name, EMaterialParameter::Texture(value));
+    }
+
+    pub fn Remove(&mut self, name: &str)
+    {
+        self.StopAnimation(name);
+
+        if self._parameters.Get(name).is_some()
+        {
+            Arc::make_mut(&mut self._parameters).Remove(name);
+        }
+    }
+
+    /// Overrides a parameter from a scene file entry written as name=value.
+    pub fn SetFromString(&mut self, entry: &str, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        match entry.split_once('=')
+        {
+            Some((name, value)) => self.Set(name.trim(), EMaterialParameter::Parse(value, display, assets)),
+            None => panic!("Material override {} should be written as name=value", entry)
+        }
+    }
+
+    /// Blends a parameter from one value to another over seconds of game time, see Update.
+    /// i.e. a hit flash: Animate("flash_color", Color([1, 1, 1, 1]), Color([1, 1, 1, 0]), 0.2)
+    pub fn Animate(&mut self, name: &str, from: EMaterialParameter, to: EMaterialParameter, seconds: f32)
+    {
+        self.StopAnimation(name);
+        self.Write(name, from.clone());
+
+        self._animations.push(
+            ParameterAnimation
+            {
+                Name: String::from(name),
+                From: from,
+                To: to,
+                Duration: seconds.max(0.0),
+                Elapsed: 0.0
+            });
+    }
+
+    /// Leaves the parameter at its current value.
+    pub fn StopAnimation(&mut self, name: &str)
+    {
+        self._animations.retain(|x| x.Name != name);
+    }
+
+    /// True while any parameter is animating.
+    pub fn HasAnimations(&self) -> bool
+    {
+        !self._animations.is_empty()
+    }
+
+    pub fn IsAnimating(&self, name: &str) -> bool
+    {
+        self._animations.iter().any(|x| x.Name == name)
+    }
+
+    /// Advances parameter animations by the frame's game time.
+    pub fn Update(&mut self, frame: &GameFrame)
+    {
+        if self._animations.is_empty()
+        {
+            return;
+        }
+
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+        let mut values = Vec::with_capacity(self._animations.len());
+
+        for animation in self._animations.iter_mut()
+        {
+            animation.Elapsed += deltaSeconds;
+
+            let t = if animation.Duration > 0.0 { (animation.Elapsed / animation.Duration).min(1.0) } else { 1.0 };
+            values.push((animation.Name.clone(), animation.From.Lerp(&animation.To, t)));
+        }
+
+        self._animations.retain(|x| x.Elapsed < x.Duration);
+
+        for (name, value) in values
+        {
+            self.Write(name.as_str(), value);
+        }
+    }
+
+    /// Copies the parameters first if another sprite still shares them.
+    fn Write(&mut self, name: &str, value: EMaterialParameter)
+    {
+        if self._parameters.Get(name) != Some(&value)
+        {
+            Arc::make_mut(&mut self._parameters).Set(name, value);
+        }
+    }
+}
+
+/// Engine uniforms followed by a batch's material parameters.
+///
+/// Programs keep uniform values between draws, so every value the program
+/// declares but nothing sets is reset to zero. A material that leaves out
+/// a parameter then never shows the previous batch's value.
+pub struct MaterialUniforms<'a, U: Uniforms>
+{
+    _base: U,
+    _parameters: Option<&'a MaterialParameters>,
+    _program: &'a Program,
+
+    /// Bound to samplers nothing sets.
+    _fallbackTexture: &'a Texture2d,
+    _sampler: SamplerBehavior
+}
+
+impl<'a, U: Uniforms> MaterialUniforms<'a, U>
+{
+    pub fn Create(
+        base: U,
+        parameters: Option<&'a MaterialParameters>,
+        program: &'a Program,
+        fallbackTexture: &'a Texture2d,
+        sampler: SamplerBehavior) -> Self
+    {
+        Self
+        {
+            _base: base,
+            _parameters: parameters,
+            _program: program,
+            _fallbackTexture: fallbackTexture,
+            _sampler: sampler
+        }
+    }
+}
+
+impl<'a, U: Uniforms> Uniforms for MaterialUniforms<'a, U>
+{
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut output: F)
+    {
+        let mut names: Vec<&str> = Vec::new();
+
+        self._base.visit_values(|name, value|
+            {
+                names.push(self._program.uniforms().find(|x| x.0 == name).map_or("", |x| x.0.as_str()));
+                output(name, value);
+            });
+
+        for (name, value) in self._parameters.iter().flat_map(|x| x.Iter())
+        {
+            let uniform = self._program.get_uniform(name);
+            let value = value.AsUniform(self._sampler);
+
+            // Parameters the shader does not declare, or declares as another type, are skipped.
+            if names.contains(&name.as_str()) || !uniform.map_or(false, |x| value.is_usable_with(&x.ty))
+            {
+                continue;
+            }
+
+            names.push(name.as_str());
+            output(name, value);
+        }
+
+        for (name, uniform) in self._program.uniforms()
+        {
+            if names.contains(&name.as_str()) || uniform.size.is_some()
+            {
+                continue;
+            }
+
+            let value = match uniform.ty
+            {
+                UniformType::Float => UniformValue::Float(0.0),
+                UniformType::FloatVec2 => UniformValue::Vec2([0.0; 2]),
+                UniformType::FloatVec3 => UniformValue::Vec3([0.0; 3]),
+                UniformType::FloatVec4 => UniformValue::Vec4([0.0; 4]),
+                UniformType::Int => UniformValue::SignedInt(0),
+                UniformType::Bool => UniformValue::Bool(false),
+                UniformType::Sampler2d => UniformValue::Texture2d(self._fallbackTexture, Some(self._sampler)),
+                _ => continue
+            };
+
+            output(name, value);
+        }
+    }
+}
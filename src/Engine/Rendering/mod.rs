@@ -1,37 +1,634 @@
-use crate::Engine::Components::RenderComponents::Renderer2D;
-use crate::Engine::Math::Float3;
-
-pub struct Renderer
-{
-    _light: Vec<LightingData>,
-    _renderers: Vec<Renderer2D>
-}
-
-impl Renderer
-{
-    pub fn Renderer(&mut self)
-    {
-
-    }
-
-    fn SortZ(&mut self)
-    {
-
-    }
-
-    fn Draw()
-    {
-
-    }
-}
-
-pub struct LightingData
-{
-    pub Position : Float3,
-    pub Color : Float3,
-    pub Intensity : f32
-}
-
-
-
-
+use std::sync::Arc;
+
+use cgmath::Matrix4;
+
+use glium::{Display, Surface, Texture2d, VertexBuffer};
+use glium::glutin::surface::WindowSurface;
+use glium::Rect;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::texture::{MipmapsOption, UncompressedFloatFormat};
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter};
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::material::{MaterialParameters, MaterialUniforms};
+use crate::Engine::Shader::{ShaderModule, DEFAULT_FRAGMENT, DEFAULT_VERTEX};
+
+pub mod debug_draw;
+pub mod material;
+pub mod post_process;
+pub mod text;
+pub mod virtual_screen;
+
+/// Most lights that can affect lit sprites in a single frame.
+/// Additional lights are ignored.
+pub const MAX_LIGHTS: usize = 32;
+
+/// Define enabling lighting in sprite shaders, added to the shader of lit quads.
+pub const LIT_DEFINE: &str = "LIT";
+
+/// DEFAULT_FRAGMENT with LIT_DEFINE, for lit quads without a material.
+pub const DEFAULT_LIT_FRAGMENT: &str = "Shaders/F_UnlitSprite.shader#LIT";
+
+/// Rows of the light texture, each light is one column.
+///     0 - position xyz, radius
+///     1 - color rgb, intensity
+///     2 - direction xy, cosine of half the cone angle, falloff
+const LIGHT_TEXTURE_ROWS: u32 = 3;
+
+/// Vertex layout shared by every batched sprite.
+#[derive(Copy, Clone)]
+pub struct SpriteVertex
+{
+    position: [f32; 3],
+    tex_coords: [f32; 2],
+    tint: [f32; 4],
+    lit: f32,
+
+    /// -1 on an axis the sprite is mirrored on, 1 otherwise.
+    uv_sign: [f32; 2],
+}
+
+implement_vertex!(SpriteVertex, position, tex_coords, tint, lit, uv_sign);
+
+/// Named groups drawn back to front, in declaration order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ESortingLayer
+{
+    Background = 0,
+    World = 1,
+    Characters = 2,
+    Foreground = 3,
+    UI = 4
+}
+
+impl ESortingLayer
+{
+    pub const COUNT: usize = 5;
+
+    /// Every layer in draw order.
+    pub const LAYERS: [ESortingLayer; Self::COUNT] =
+        [Self::Background, Self::World, Self::Characters, Self::Foreground, Self::UI];
+
+    /// Mask containing every layer.
+    pub const ALL: u32 = (1 << Self::COUNT) - 1;
+
+    /// Bit of this layer within a layer mask.
+    pub fn Mask(self) -> u32
+    {
+        1 << self as u32
+    }
+
+    /// Parses a comma separated list of layer names into a mask, i.e. "World, Characters".
+    /// "All" selects every layer.
+    pub fn MaskFromString(value: &str) -> u32
+    {
+        if value.trim().eq_ignore_ascii_case("all")
+        {
+            return Self::ALL;
+        }
+
+        value
+            .split(',')
+            .filter(|x| !x.trim().is_empty())
+            .fold(0, |mask, x| mask | Self::FromString(x).Mask())
+    }
+
+    /// Parses a layer name as written in scene files, i.e. "Background".
+    pub fn FromString(value: &str) -> Self
+    {
+        match value.trim().to_lowercase().as_str()
+        {
+            "background" => ESortingLayer::Background,
+            "world" => ESortingLayer::World,
+            "characters" => ESortingLayer::Characters,
+            "foreground" => ESortingLayer::Foreground,
+            "ui" => ESortingLayer::UI,
+            x => panic!("Unknown sorting layer {}", x)
+        }
+    }
+}
+
+/// A textured quad submitted for drawing this frame.
+pub struct SpriteQuad
+{
+    pub Texture: Arc<Texture2d>,
+
+    /// Normal map sharing the texture's layout, for lit sprites.
+    pub NormalMap: Option<Arc<Texture2d>>,
+
+    /// Fragment shader to draw with, a variant including LIT_DEFINE when IsLit. See ShaderModule::Variant.
+    pub Shader: String,
+
+    /// Material parameters set as uniforms of the shader, None for the engine uniforms only.
+    pub Parameters: Option<Arc<MaterialParameters>>,
+
+    /// World positions of the corners.
+    /// Bottom left, bottom right, top right, top left.
+    pub Corners: [Float3; 4],
+
+    /// Texture coordinates of the bottom left and top right corners.
+    pub UVMin: [f32; 2],
+    pub UVMax: [f32; 2],
+
+    pub Tint: [f32; 4],
+    pub IsLit: bool,
+
+    /// How much of each view's movement the quad follows per axis, 1 for world sprites.
+    /// Below 1 the quad is moved along with every view drawing it, see ParallaxLayer.
+    pub ScrollFactor: Float3,
+
+    pub Layer: ESortingLayer,
+
+    /// Draw order within the layer, higher values are drawn on top.
+    pub OrderInLayer: i32
+}
+
+impl SpriteQuad
+{
+    /// Point the quad is sorted by, the center of its corners.
+    fn Pivot(&self) -> Float3
+    {
+        Float3::scale_value(
+            self.Corners[0] + self.Corners[1] + self.Corners[2] + self.Corners[3],
+            0.25)
+    }
+}
+
+/// One camera's pass over the submitted quads.
+pub struct RenderView
+{
+    pub View: Matrix4<f32>,
+    pub Projection: Matrix4<f32>,
+
+    /// Area of the target drawn into, in pixels.
+    pub Viewport: Rect,
+
+    /// Sorting layers drawn by this view, see ESortingLayer::Mask.
+    pub LayerMask: u32,
+
+    /// World position the view looks from, parallax quads are offset by it.
+    pub Position: Float3,
+
+    /// Color the viewport is cleared to first. None draws over what is already there.
+    pub ClearColor: Option<(f32, f32, f32, f32)>
+}
+
+/// Quads that share a texture, normal map, shader and parameters, drawn with a single call.
+struct SpriteBatch
+{
+    Texture: Arc<Texture2d>,
+    NormalMap: Option<Arc<Texture2d>>,
+    Shader: String,
+    Parameters: Option<Arc<MaterialParameters>>,
+    IsLit: bool,
+    Vertices: Vec<SpriteVertex>
+}
+
+/// Collects sprites submitted during the frame and draws them in batches.
+///
+/// Before drawing, the queue is sorted back to front by layer, order in layer,
+/// depth and optionally height. Neighbouring quads that share a texture and
+/// shader are then drawn with a single call.
+pub struct Renderer
+{
+    _light: Vec<LightingData>,
+
+    /// Light applied to lit sprites regardless of any light source.
+    _ambientLight: Float3,
+
+    /// Lights packed into a float texture so shaders can loop over them.
+    _lightTexture: Option<Texture2d>,
+    _quads: Vec<SpriteQuad>,
+
+    /// Layers where lower sprites are drawn in front of higher ones.
+    _ySortedLayers: [bool; ESortingLayer::COUNT],
+
+    /// Reused between frames, grown when a batch no longer fits.
+    _vertexBuffers: Vec<VertexBuffer<SpriteVertex>>,
+
+    /// Rounds sprite corners to whole world pixels to avoid sub pixel shimmer.
+    pub PixelSnap: bool,
+
+    _drawCalls: usize,
+    _quadCount: usize
+}
+
+impl Renderer
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            _light: Vec::new(),
+            _ambientLight: Float3::one(),
+            _lightTexture: None,
+            _quads: Vec::new(),
+            _ySortedLayers: [false; ESortingLayer::COUNT],
+            _vertexBuffers: Vec::new(),
+            PixelSnap: true,
+            _drawCalls: 0,
+            _quadCount: 0
+        }
+    }
+
+    /// Queues a quad to be drawn at the end of the frame.
+    pub fn Submit(&mut self, quad: SpriteQuad)
+    {
+        self._quads.push(quad);
+    }
+
+    /// Queues a light for the current frame.
+    pub fn SubmitLight(&mut self, light: LightingData)
+    {
+        self._light.push(light);
+    }
+
+    /// Sets the light lit sprites receive with no light source nearby.
+    pub fn SetAmbientLight(&mut self, color: Float3)
+    {
+        self._ambientLight = color;
+    }
+
+    /// Sorts sprites in a layer by height, for top down scenes where
+    /// sprites further down the screen should overlap those behind them.
+    pub fn SetYSort(&mut self, layer: ESortingLayer, isEnabled: bool)
+    {
+        self._ySortedLayers[layer as usize] = isEnabled;
+    }
+
+    /// Number of draw calls issued by the last Draw.
+    pub fn DrawCallCount(&self) -> usize
+    {
+        self._drawCalls
+    }
+
+    /// Number of quads drawn by the last Draw.
+    pub fn QuadCount(&self) -> usize
+    {
+        self._quadCount
+    }
+
+    /// Draws every quad submitted this frame once per view, then clears the queue.
+    /// Views are drawn in the order given, each only seeing the layers in its mask.
+    /// Target is either the window or an offscreen framebuffer.
+    pub fn Draw<S: Surface>(
+        &mut self,
+        display: &Display<WindowSurface>,
+        target: &mut S,
+        shaders: &mut ShaderModule,
+        frame: &GameFrame,
+        views: &[RenderView])
+    {
+        self.SortZ();
+        let lightCount = self.UploadLights(display);
+
+        self._quadCount = 0;
+        self._drawCalls = 0;
+
+        // Sprite corners are already in world units, the camera does the rest.
+        let model =
+            [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0f32],
+            ];
+
+        let ambient = [self._ambientLight.x(), self._ambientLight.y(), self._ambientLight.z()];
+
+        let lightBehavior = glium::uniforms::SamplerBehavior
+        {
+            minify_filter: MinifySamplerFilter::Nearest,
+            magnify_filter: MagnifySamplerFilter::Nearest,
+            ..Default::default()
+        };
+
+        let behavior = glium::uniforms::SamplerBehavior
+        {
+            minify_filter: MinifySamplerFilter::Nearest,
+            magnify_filter: MagnifySamplerFilter::Nearest,
+            ..Default::default()
+        };
+
+        // Every batch this frame gets its own buffer, views never overwrite one another.
+        let mut bufferIndex = 0;
+
+        for view in views
+        {
+            if let Some(c) = view.ClearColor
+            {
+                target.clear(Some(&view.Viewport), Some(c), false, None, None);
+            }
+
+            let batches = self.Batch(view);
+            self._quadCount += batches.iter().map(|x| x.Vertices.len() / 6).sum::<usize>();
+
+            let view_mat : [[f32;4];4] = view.View.into();
+            let perspective_mat : [[f32;4];4] = view.Projection.into();
+
+            // Draw order comes from the sorted queue, so blend instead of depth testing.
+            let params = glium::DrawParameters{
+                blend: glium::Blend::alpha_blending(),
+                viewport: Some(view.Viewport),
+                .. Default::default()
+            };
+
+            for batch in batches.iter()
+            {
+                // Sprites whose shader never compiled fall back to the default one, lit or not.
+                let shader = match shaders.Program(display, DEFAULT_VERTEX, &batch.Shader)
+                {
+                    Some(_) => batch.Shader.clone(),
+                    None if batch.IsLit => String::from(DEFAULT_LIT_FRAGMENT),
+                    None => String::from(DEFAULT_FRAGMENT)
+                };
+
+                let program = match shaders.Program(display, DEFAULT_VERTEX, &shader)
+                {
+                    Some(x) => x,
+                    None => continue
+                };
+
+                self.FillVertexBuffer(display, bufferIndex, &batch.Vertices);
+
+                let lights = self._lightTexture.as_ref().unwrap();
+
+                // Samplers need a texture bound, unused when has_normal_map is false.
+                let normalMap = match &batch.NormalMap
+                {
+                    Some(x) => x.as_ref(),
+                    None => batch.Texture.as_ref()
+                };
+                let vertices = self._vertexBuffers[bufferIndex]
+                    .slice(0 .. batch.Vertices.len())
+                    .unwrap();
+
+                let engineUniforms = uniform!
+                {
+                    view: view_mat,
+                    model: model,
+                    perspective: perspective_mat,
+                    tex: glium::uniforms::Sampler(batch.Texture.as_ref(), behavior),
+                    normal_map: glium::uniforms::Sampler(normalMap, behavior),
+                    has_normal_map: batch.NormalMap.is_some(),
+                    time: frame.GameTime.num_milliseconds() as i32,
+                    lights: glium::uniforms::Sampler(lights, lightBehavior),
+                    light_count: lightCount as i32,
+                    ambient_light: ambient,
+                };
+
+                let uniforms = MaterialUniforms::Create(
+                    engineUniforms,
+                    batch.Parameters.as_deref(),
+                    program,
+                    batch.Texture.as_ref(),
+                    behavior);
+
+                target.draw(
+                    vertices,
+                    NoIndices(PrimitiveType::TrianglesList),
+                    program,
+                    &uniforms,
+                    &params).unwrap();
+
+                bufferIndex += 1;
+                self._drawCalls += 1;
+            }
+        }
+
+        self._quads.clear();
+    }
+
+    /// Writes this frame's lights into the light texture and clears the queue.
+    /// Returns the number of lights written.
+    fn UploadLights(&mut self, display: &Display<WindowSurface>) -> usize
+    {
+        if self._lightTexture.is_none()
+        {
+            self._lightTexture = Some(
+                Texture2d::empty_with_format(
+                    display,
+                    UncompressedFloatFormat::F32F32F32F32,
+                    MipmapsOption::NoMipmap,
+                    MAX_LIGHTS as u32,
+                    LIGHT_TEXTURE_ROWS).unwrap());
+        }
+
+        let count = self._light.len().min(MAX_LIGHTS);
+
+        if count == 0
+        {
+            self._light.clear();
+            return 0;
+        }
+
+        let mut rows: Vec<Vec<(f32, f32, f32, f32)>> = vec![Vec::new(); LIGHT_TEXTURE_ROWS as usize];
+
+        for light in self._light.iter().take(count)
+        {
+            let direction = light.Direction.normalized();
+            // Point lights use -1 so every angle passes the cone test.
+            let coneCosine =
+                if light.ConeAngle >= 360.0 { -1.0 } else { (light.ConeAngle * 0.5).to_radians().cos() };
+
+            rows[0].push((light.Position.x(), light.Position.y(), light.Position.z(), light.Radius));
+            rows[1].push((light.Color.x(), light.Color.y(), light.Color.z(), light.Intensity));
+            rows[2].push((direction.x(), direction.y(), coneCosine, light.Falloff));
+        }
+
+        self._lightTexture.as_ref().unwrap().write(
+            Rect { left: 0, bottom: 0, width: count as u32, height: LIGHT_TEXTURE_ROWS },
+            rows);
+
+        self._light.clear();
+
+        count
+    }
+
+    /// Orders the queue back to front.
+    ///
+    /// Layer, then order in layer, then depth with the farthest first.
+    /// Y sorted layers draw higher sprites first before falling back to depth.
+    /// The sort is stable so ties keep their submission order.
+    fn SortZ(&mut self)
+    {
+        let ySortedLayers = self._ySortedLayers;
+
+        self._quads.sort_by(|a, b|
+            {
+                let pivotA = a.Pivot();
+                let pivotB = b.Pivot();
+
+                let mut order = a.Layer.cmp(&b.Layer)
+                    .then(a.OrderInLayer.cmp(&b.OrderInLayer));
+
+                if ySortedLayers[a.Layer as usize]
+                {
+                    order = order.then(pivotB.y().total_cmp(&pivotA.y()));
+                }
+
+                order.then(pivotA.z().total_cmp(&pivotB.z()))
+            });
+    }
+
+    /// Groups neighbouring quads in the view's mask that share a texture, normal map, shader and parameters.
+    /// Quads are only merged with the previous batch so the sorted order is kept.
+    fn Batch(&self, view: &RenderView) -> Vec<SpriteBatch>
+    {
+        let mut batches: Vec<SpriteBatch> = Vec::new();
+
+        for quad in self._quads.iter().filter(|x| x.Layer.Mask() & view.LayerMask != 0)
+        {
+            let canMerge = match batches.last()
+            {
+                Some(x) =>
+                    Arc::ptr_eq(&x.Texture, &quad.Texture) &&
+                    SameNormalMap(&x.NormalMap, &quad.NormalMap) &&
+                    x.Shader == quad.Shader &&
+                    x.IsLit == quad.IsLit &&
+                    SameParameters(&x.Parameters, &quad.Parameters),
+                None => false
+            };
+
+            if !canMerge
+            {
+                batches.push(
+                    SpriteBatch
+                    {
+                        Texture: quad.Texture.clone(),
+                        NormalMap: quad.NormalMap.clone(),
+                        Shader: quad.Shader.clone(),
+                        Parameters: quad.Parameters.clone(),
+                        IsLit: quad.IsLit,
+                        Vertices: Vec::new()
+                    });
+            }
+
+            // The part of the view's movement the quad doesn't follow.
+            let offset = Float3::new(
+                view.Position.x() * (1.0 - quad.ScrollFactor.x()),
+                view.Position.y() * (1.0 - quad.ScrollFactor.y()),
+                0.0);
+
+            Self::AppendQuad(&mut batches.last_mut().unwrap().Vertices, quad, offset, self.PixelSnap);
+        }
+
+        batches
+    }
+
+    /// Adds the two triangles of a quad to a vertex list.
+    fn AppendQuad(vertices: &mut Vec<SpriteVertex>, quad: &SpriteQuad, offset: Float3, pixelSnap: bool)
+    {
+        let uv =
+            [
+                [quad.UVMin[0], quad.UVMin[1]],
+                [quad.UVMax[0], quad.UVMin[1]],
+                [quad.UVMax[0], quad.UVMax[1]],
+                [quad.UVMin[0], quad.UVMax[1]],
+            ];
+
+        let uvSign =
+            [
+                if quad.UVMax[0] < quad.UVMin[0] {-1.0} else {1.0},
+                if quad.UVMax[1] < quad.UVMin[1] {-1.0} else {1.0},
+            ];
+
+        for i in [0, 1, 2, 2, 3, 0]
+        {
+            let corner = quad.Corners[i] + offset;
+            let (x, y) =
+                if pixelSnap
+                {
+                    (
+                        (corner.x() * PIXELS_PER_UNIT).round() / PIXELS_PER_UNIT,
+                        (corner.y() * PIXELS_PER_UNIT).round() / PIXELS_PER_UNIT
+                    )
+                }
+                else
+                {
+                    (corner.x(), corner.y())
+                };
+
+            vertices.push(
+                SpriteVertex
+                {
+                    position: [x, y, corner.z()],
+                    tex_coords: uv[i],
+                    tint: quad.Tint,
+                    lit: if quad.IsLit {1.0} else {0.0},
+                    uv_sign: uvSign
+                });
+        }
+    }
+
+    fn FillVertexBuffer(&mut self, display: &Display<WindowSurface>, index: usize, vertices: &Vec<SpriteVertex>)
+    {
+        let needsBuffer = match self._vertexBuffers.get(index)
+        {
+            Some(x) => x.len() < vertices.len(),
+            None => true
+        };
+
+        if needsBuffer
+        {
+            let buffer = VertexBuffer::empty_dynamic(display, vertices.len().next_power_of_two()).unwrap();
+
+            if index < self._vertexBuffers.len()
+            {
+                self._vertexBuffers[index] = buffer;
+            }
+            else
+            {
+                self._vertexBuffers.push(buffer);
+            }
+        }
+
+        self._vertexBuffers[index]
+            .slice(0 .. vertices.len())
+            .unwrap()
+            .write(vertices);
+    }
+}
+
+fn SameNormalMap(a: &Option<Arc<Texture2d>>, b: &Option<Arc<Texture2d>>) -> bool
+{
+    match (a, b)
+    {
+        (Some(x), Some(y)) => Arc::ptr_eq(x, y),
+        (None, None) => true,
+        _ => false
+    }
+}
+
+/// Parameters shared or equal in value, having none is the same as an empty set.
+fn SameParameters(a: &Option<Arc<MaterialParameters>>, b: &Option<Arc<MaterialParameters>>) -> bool
+{
+    match (a, b)
+    {
+        (Some(x), Some(y)) => Arc::ptr_eq(x, y) || x == y,
+        (Some(x), None) | (None, Some(x)) => x.IsEmpty(),
+        (None, None) => true
+    }
+}
+
+/// A light submitted for the current frame.
+pub struct LightingData
+{
+    pub Position : Float3,
+    pub Color : Float3,
+    pub Intensity : f32,
+
+    /// Distance in world units at which the light has no effect.
+    pub Radius : f32,
+
+    /// Exponent of the fade towards the radius.
+    pub Falloff : f32,
+
+    /// Direction of a spot light.
+    pub Direction : Float3,
+
+    /// Full cone angle in degrees, 360 for a point light.
+    pub ConeAngle : f32
+}
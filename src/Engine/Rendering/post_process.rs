@@ -0,0 +1,283 @@
+use std::fs;
+use std::path::Path;
+
+use glium::{Display, DrawParameters, Frame, Program, Rect, Surface, Texture2d, VertexBuffer};
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::glutin::surface::WindowSurface;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction};
+
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::SceneBuilder::PROPERTY_SEPARATOR;
+use crate::Engine::Shader::ShaderModule;
+
+/// Vertex shader shared by every full screen pass.
+pub const POST_PROCESS_VERTEX: &str = "V_PostProcess.shader";
+
+/// Default pass chain, loaded by the game on start.
+pub const DEFAULT_CHAIN: &str = "Shaders/post_process.chain";
+
+#[derive(Copy, Clone)]
+struct ScreenVertex
+{
+    position: [f32; 2],
+    tex_coords: [f32; 2],
+}
+
+implement_vertex!(ScreenVertex, position, tex_coords);
+
+/// A full screen shader applied to the rendered scene.
+pub struct PostProcessPass
+{
+    pub Name: String,
+
+    /// Fragment shader file name within the Shaders folder.
+    pub Shader: String,
+
+    pub IsEnabled: bool,
+
+    /// Pass specific amount, i.e. vignette darkness or palette levels.
+    pub Strength: f32
+}
+
+/// Runs the rendered scene through a chain of full screen passes.
+///
+/// Enabled passes run in order at the scene's resolution, each reading
+/// the previous pass's output. The last enabled pass draws to the window.
+pub struct PostProcessStack
+{
+    _passes: Vec<PostProcessPass>,
+
+    /// Two textures passes alternate between.
+    _targets: Vec<Texture2d>,
+    _targetSize: (u32, u32),
+
+    _screenQuad: Option<VertexBuffer<ScreenVertex>>
+}
+
+impl PostProcessStack
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            _passes: Vec::new(),
+            _targets: Vec::new(),
+            _targetSize: (0, 0),
+            _screenQuad: None
+        }
+    }
+
+    /// Loads a pass chain from file.
+    ///
+    /// File Structure:
+    ///     Pass | [name] | [fragment shader] | [enabled (true/false)] | [strength]
+    pub fn FromFile(path: &str) -> Self
+    {
+        let contents = match fs::read_to_string(path)
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read post process chain: {} \n {}", path, err)
+        };
+
+        let mut stack = Self::Create();
+
+        let entries = contents
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty() && !x.starts_with("//"));
+
+        for entry in entries
+        {
+            let tokens: Vec<&str> = entry
+                .split(PROPERTY_SEPARATOR)
+                .map(|x| x.trim())
+                .collect();
+
+            match tokens[0]
+            {
+                "Pass" =>
+                    {
+                        if tokens.len() < 4
+                        {
+                            panic!("{}: Pass {} is missing properties", path, tokens[1]);
+                        }
+
+                        let strength = match tokens.get(4)
+                        {
+                            Some(x) => x.parse().unwrap(),
+                            None => 1.0
+                        };
+
+                        stack.AddPass(tokens[1], tokens[2], tokens[3].parse().unwrap(), strength);
+                    }
+                x => panic!("{}: Unknown post process entry {}", path, x)
+            }
+        }
+
+        stack
+    }
+
+    /// Appends a pass to the end of the chain.
+    pub fn AddPass(&mut self, name: &str, shader: &str, isEnabled: bool, strength: f32)
+    {
+        self._passes.push(
+            PostProcessPass
+            {
+                Name: String::from(name),
+                Shader: String::from(shader),
+                IsEnabled: isEnabled,
+                Strength: strength
+            });
+    }
+
+    pub fn Passes(&self) -> &Vec<PostProcessPass>
+    {
+        &self._passes
+    }
+
+    pub fn Pass(&mut self, name: &str) -> Option<&mut PostProcessPass>
+    {
+        self._passes.iter_mut().find(|x| x.Name == name)
+    }
+
+    /// Flips a pass on or off. Returns the new state.
+    pub fn TogglePass(&mut self, name: &str) -> bool
+    {
+        match self.Pass(name)
+        {
+            Some(x) =>
+                {
+                    x.IsEnabled = !x.IsEnabled;
+                    x.IsEnabled
+                },
+            None => false
+        }
+    }
+
+    /// True if any enabled pass has a working program, call after Prepare.
+    pub fn IsActive(&self, shaders: &ShaderModule) -> bool
+    {
+        !self.UsablePasses(shaders).is_empty()
+    }
+
+    /// Makes sure the pass textures match the scene size and every enabled pass is compiled.
+    /// Call before IsActive and Apply each frame.
+    pub fn Prepare(&mut self, display: &Display<WindowSurface>, shaders: &mut ShaderModule, size: (u32, u32))
+    {
+        if self._targets.is_empty() || self._targetSize != size
+        {
+            self._targets = (0..2)
+                .map(|_| Texture2d::empty(display, size.0.max(1), size.1.max(1)).unwrap())
+                .collect();
+
+            self._targetSize = size;
+        }
+
+        if self._screenQuad.is_none()
+        {
+            let shape = vec![
+                ScreenVertex { position: [-1.0, -1.0], tex_coords: [0.0, 0.0] },
+                ScreenVertex { position: [ 1.0, -1.0], tex_coords: [1.0, 0.0] },
+                ScreenVertex { position: [-1.0,  1.0], tex_coords: [0.0, 1.0] },
+                ScreenVertex { position: [ 1.0,  1.0], tex_coords: [1.0, 1.0] },
+            ];
+
+            self._screenQuad = Some(VertexBuffer::new(display, &shape).unwrap());
+        }
+
+        // Passes that fail to compile are skipped, the error is reported by the shader module.
+        for pass in self._passes.iter().filter(|x| x.IsEnabled)
+        {
+            shaders.Program(display, ShaderPath(POST_PROCESS_VERTEX).as_str(), ShaderPath(pass.Shader.as_str()).as_str());
+        }
+    }
+
+    /// Runs every enabled pass over the scene and draws the result into the viewport of target.
+    pub fn Apply(
+        &self,
+        display: &Display<WindowSurface>,
+        scene: &Texture2d,
+        target: &mut Frame,
+        viewport: Rect,
+        shaders: &ShaderModule,
+        frame: &GameFrame)
+    {
+        let enabled = self.UsablePasses(shaders);
+
+        let quad = self._screenQuad.as_ref().unwrap();
+        let indices = NoIndices(PrimitiveType::TriangleStrip);
+
+        let behavior = glium::uniforms::SamplerBehavior
+        {
+            minify_filter: MinifySamplerFilter::Nearest,
+            magnify_filter: MagnifySamplerFilter::Nearest,
+            wrap_function: (SamplerWrapFunction::Clamp, SamplerWrapFunction::Clamp, SamplerWrapFunction::Clamp),
+            ..Default::default()
+        };
+
+        let resolution = [self._targetSize.0 as f32, self._targetSize.1 as f32];
+        let mut source: Option<usize> = None;
+
+        for (step, passIndex) in enabled.iter().enumerate()
+        {
+            let pass = &self._passes[*passIndex];
+            let program = self.PassProgram(shaders, pass).unwrap();
+
+            let input = match source
+            {
+                Some(x) => &self._targets[x],
+                None => scene
+            };
+
+            let uniforms = uniform!
+            {
+                tex: glium::uniforms::Sampler(input, behavior),
+                scene: glium::uniforms::Sampler(scene, behavior),
+                resolution: resolution,
+                strength: pass.Strength,
+                time: frame.GameTime.num_milliseconds() as i32,
+            };
+
+            if step == enabled.len() - 1
+            {
+                let params = DrawParameters
+                {
+                    viewport: Some(viewport),
+                    .. Default::default()
+                };
+
+                target.draw(quad, &indices, program, &uniforms, &params).unwrap();
+            }
+            else
+            {
+                // Alternate between the two spare textures, never writing the one being read.
+                let destination = if source == Some(0) { 1 } else { 0 };
+
+                let mut buffer = SimpleFrameBuffer::new(display, &self._targets[destination]).unwrap();
+                buffer.draw(quad, &indices, program, &uniforms, &Default::default()).unwrap();
+
+                source = Some(destination);
+            }
+        }
+    }
+
+    /// Enabled passes with a compiled program, in chain order.
+    fn UsablePasses(&self, shaders: &ShaderModule) -> Vec<usize>
+    {
+        (0..self._passes.len())
+            .filter(|x| self._passes[*x].IsEnabled && self.PassProgram(shaders, &self._passes[*x]).is_some())
+            .collect()
+    }
+
+    fn PassProgram<'a>(&self, shaders: &'a ShaderModule, pass: &PostProcessPass) -> Option<&'a Program>
+    {
+        shaders.GetProgram(ShaderPath(POST_PROCESS_VERTEX).as_str(), ShaderPath(pass.Shader.as_str()).as_str())
+    }
+}
+
+/// Name ShaderModule stores a file in the Shaders folder under.
+pub(crate) fn ShaderPath(fileName: &str) -> String
+{
+    String::from(Path::new("Shaders").join(fileName).to_str().unwrap())
+}
@@ -0,0 +1,619 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use ab_glyph::{Font as TFont, FontVec, PxScale, ScaleFont};
+use glium::{Display, Texture2d};
+use glium::glutin::surface::WindowSurface;
+use glium::texture::RawImage2d;
+
+use crate::Engine::Components::RenderComponents::Sprite;
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, Renderer, SpriteQuad};
+use crate::Engine::Shader::DEFAULT_FRAGMENT;
+
+/// Characters rasterized from TrueType fonts, printable ASCII and Latin-1.
+const TRUE_TYPE_CHARSET: [(u32, u32); 2] = [(32, 126), (160, 255)];
+
+/// Width of the atlas TrueType glyphs are packed into.
+const ATLAS_WIDTH: u32 = 512;
+
+/// Empty pixels between packed glyphs so neighbours never bleed into each other.
+const ATLAS_PADDING: u32 = 1;
+
+/// Where a character sits in the font atlas and how to place it.
+/// Sizes are in font pixels.
+#[derive(Copy, Clone, Debug)]
+pub struct Glyph
+{
+    pub UVMin: [f32; 2],
+    pub UVMax: [f32; 2],
+    pub Size: (f32, f32),
+
+    /// Offset from the pen position to the top left of the glyph, y grows downwards.
+    pub Offset: (f32, f32),
+
+    /// Distance the pen moves after this glyph.
+    pub Advance: f32
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ETextAlign
+{
+    Left,
+    Center,
+    Right
+}
+
+/// How a block of text is laid out and drawn.
+#[derive(Copy, Clone, Debug)]
+pub struct TextStyle
+{
+    pub Color: [f32; 4],
+
+    /// Horizontal alignment of each line around the text position.
+    pub Align: ETextAlign,
+
+    /// Multiplier of the font's pixel size. Whole numbers keep pixel fonts crisp.
+    pub Scale: f32,
+
+    /// Lines wrap at spaces once they are wider than this, in world units.
+    pub MaxWidth: Option<f32>,
+
+    /// Multiplier of the font's line height.
+    pub LineSpacing: f32,
+
+    /// UI draws in screen space through the UI camera, every other layer in world space.
+    pub Layer: ESortingLayer,
+    pub OrderInLayer: i32
+}
+
+impl TextStyle
+{
+    /// White, left aligned screen space text.
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            Color: [1.0, 1.0, 1.0, 1.0],
+            Align: ETextAlign::Left,
+            Scale: 1.0,
+            MaxWidth: None,
+            LineSpacing: 1.0,
+            Layer: ESortingLayer::UI,
+            OrderInLayer: 0
+        }
+    }
+}
+
+/// A glyph positioned by Font::Layout, in font pixels relative to the top left of the text.
+pub struct PlacedGlyph
+{
+    pub Glyph: Glyph,
+    pub X: f32,
+    pub Y: f32
+}
+
+/// Glyph atlas and metrics for drawing text, loaded from a BMFont or TrueType file.
+pub struct Font
+{
+    pub Name: String,
+    pub Texture: Arc<Texture2d>,
+
+    /// Distance between the tops of two lines, in font pixels.
+    pub LineHeight: f32,
+
+    /// Distance from the top of a line to the baseline, in font pixels.
+    pub Base: f32,
+
+    _glyphs: HashMap<char, Glyph>,
+    _kerning: HashMap<(char, char), f32>
+}
+
+impl Font
+{
+    /// Loads a font, BMFont for .fnt files and TrueType otherwise.
+    /// pixelSize is only used for TrueType fonts, bitmap fonts have a fixed size.
+    pub fn FromFile(path: &str, pixelSize: f32, antialiased: bool, display: &Display<WindowSurface>) -> Self
+    {
+        if path.trim().to_lowercase().ends_with(".fnt")
+        {
+            Self::FromBMFont(path, display)
+        }
+        else
+        {
+            Self::FromTrueType(path, pixelSize, antialiased, display)
+        }
+    }
+
+    /// Loads an AngelCode BMFont in the text format with a single page.
+    /// The page image is looked up next to the .fnt file.
+    pub fn FromBMFont(path: &str, display: &Display<WindowSurface>) -> Self
+    {
+        let contents = match fs::read_to_string(path.trim())
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read font: {} \n {}", path, err)
+        };
+
+        let mut name = String::from(path.trim());
+        let mut lineHeight = 0.0;
+        let mut base = 0.0;
+        let mut scale = (1.0, 1.0);
+        let mut pagePath: Option<String> = None;
+        let mut chars: Vec<HashMap<String, String>> = Vec::new();
+        let mut kerning = HashMap::new();
+
+        for line in contents.lines()
+        {
+            let mut tokens = line.trim().splitn(2, ' ');
+            let tag = tokens.next().unwrap_or("");
+            let values = BMFontValues(tokens.next().unwrap_or(""));
+
+            let number = |key: &str| -> f32
+                {
+                    match values.get(key)
+                    {
+                        Some(x) => x.parse().unwrap(),
+                        None => panic!("{}: {} is missing {}", path, tag, key)
+                    }
+                };
+
+            match tag
+            {
+                "info" =>
+                    {
+                        if let Some(x) = values.get("face")
+                        {
+                            name = x.clone();
+                        }
+                    }
+                "common" =>
+                    {
+                        lineHeight = number("lineHeight");
+                        base = number("base");
+                        scale = (number("scaleW"), number("scaleH"));
+
+                        if number("pages") > 1.0
+                        {
+                            panic!("{}: Only single page fonts are supported", path);
+                        }
+                    }
+                "page" => pagePath = values.get("file").cloned(),
+                "char" => chars.push(values),
+                "kerning" =>
+                    {
+                        let first = char::from_u32(number("first") as u32);
+                        let second = char::from_u32(number("second") as u32);
+
+                        if let (Some(a), Some(b)) = (first, second)
+                        {
+                            kerning.insert((a, b), number("amount"));
+                        }
+                    }
+                _ => {}
+            }
+        }
+
+        let pagePath = match pagePath
+        {
+            Some(x) => x,
+            None => panic!("{}: Font has no page", path)
+        };
+
+        let directory = Path::new(path.trim()).parent().unwrap_or(Path::new(""));
+        let texture = Sprite::LoadTexture(directory.join(pagePath).to_str().unwrap(), display);
+
+        let mut glyphs = HashMap::new();
+
+        for values in chars.iter()
+        {
+            let number = |key: &str| -> f32 { values.get(key).map(|x| x.parse().unwrap()).unwrap_or(0.0) };
+
+            let id = match char::from_u32(number("id") as u32)
+            {
+                Some(x) => x,
+                None => continue
+            };
+
+            let (x, y) = (number("x"), number("y"));
+            let (width, height) = (number("width"), number("height"));
+
+            glyphs.insert(id,
+                Glyph
+                {
+                    // Textures are stored bottom up.
+                    UVMin: [x / scale.0, 1.0 - (y + height) / scale.1],
+                    UVMax: [(x + width) / scale.0, 1.0 - y / scale.1],
+                    Size: (width, height),
+                    Offset: (number("xoffset"), number("yoffset")),
+                    Advance: number("xadvance")
+                });
+        }
+
+        Self
+        {
+            Name: name,
+            Texture: Arc::new(texture),
+            LineHeight: lineHeight,
+            Base: base,
+            _glyphs: glyphs,
+            _kerning: kerning
+        }
+    }
+
+    /// Rasterizes a TrueType or OpenType font at a pixel size into a glyph atlas.
+    ///
+    /// antialiased - Keeps smooth edges. Off, every pixel is either fully drawn or
+    ///               empty, which suits pixel art scenes.
+    pub fn FromTrueType(path: &str, pixelSize: f32, antialiased: bool, display: &Display<WindowSurface>) -> Self
+    {
+        let data = match fs::read(path.trim())
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read font: {} \n {}", path, err)
+        };
+
+        let font = match FontVec::try_from_vec(data)
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to parse font: {} \n {}", path, err)
+        };
+
+        let scaled = font.as_scaled(PxScale::from(pixelSize));
+        let ascent = scaled.ascent();
+
+        let characters: Vec<char> = TRUE_TYPE_CHARSET
+            .iter()
+            .flat_map(|x| x.0..=x.1)
+            .filter_map(char::from_u32)
+            .collect();
+
+        // Rasterize every glyph, then pack them into rows of the atlas.
+        let mut bitmaps: Vec<(char, Glyph, u32, u32, Vec<u8>)> = Vec::new();
+
+        for c in characters.iter()
+        {
+            let id = font.glyph_id(*c);
+
+            if id.0 == 0 && *c != ' '
+            {
+                continue;
+            }
+
+            let advance = scaled.h_advance(id).round();
+            let glyph = id.with_scale_and_position(PxScale::from(pixelSize), ab_glyph::point(0.0, ascent.round()));
+
+            let outline = match font.outline_glyph(glyph)
+            {
+                Some(x) => x,
+                None =>
+                    {
+                        // Spaces have no outline, only an advance.
+                        bitmaps.push((*c, Self::EmptyGlyph(advance), 0, 0, Vec::new()));
+                        continue;
+                    }
+            };
+
+            let bounds = outline.px_bounds();
+            let width = bounds.width() as u32;
+            let height = bounds.height() as u32;
+            let mut coverage = vec![0u8; (width * height) as usize];
+
+            outline.draw(|x, y, value|
+                {
+                    let alpha = if antialiased { value } else if value >= 0.5 { 1.0 } else { 0.0 };
+                    coverage[(y * width + x) as usize] = (alpha.clamp(0.0, 1.0) * 255.0) as u8;
+                });
+
+            let mut glyph = Self::EmptyGlyph(advance);
+            glyph.Size = (width as f32, height as f32);
+            glyph.Offset = (bounds.min.x, bounds.min.y);
+
+            bitmaps.push((*c, glyph, width, height, coverage));
+        }
+
+        // Shelf packing, glyphs are placed left to right and wrap onto a new row.
+        let mut positions = Vec::with_capacity(bitmaps.len());
+        let (mut x, mut y, mut rowHeight) = (ATLAS_PADDING, ATLAS_PADDING, 0);
+
+        for (_, _, width, height, _) in bitmaps.iter()
+        {
+            if x + width + ATLAS_PADDING > ATLAS_WIDTH
+            {
+                x = ATLAS_PADDING;
+                y += rowHeight + ATLAS_PADDING;
+                rowHeight = 0;
+            }
+
+            positions.push((x, y));
+            x += width + ATLAS_PADDING;
+            rowHeight = rowHeight.max(*height);
+        }
+
+        let atlasHeight = (y + rowHeight + ATLAS_PADDING).next_power_of_two();
+
+        // White pixels with coverage as alpha, so the text color comes from the tint.
+        // Rows are written bottom up to match textures loaded from images.
+        let mut pixels = vec![0u8; (ATLAS_WIDTH * atlasHeight * 4) as usize];
+        let mut glyphs = HashMap::new();
+
+        for ((c, mut glyph, width, height, coverage), (left, top)) in bitmaps.into_iter().zip(positions)
+        {
+            for row in 0..height
+            {
+                for column in 0..width
+                {
+                    let index = (((atlasHeight - 1 - (top + row)) * ATLAS_WIDTH + left + column) * 4) as usize;
+
+                    pixels[index] = 255;
+                    pixels[index + 1] = 255;
+                    pixels[index + 2] = 255;
+                    pixels[index + 3] = coverage[(row * width + column) as usize];
+                }
+            }
+
+            glyph.UVMin = [left as f32 / ATLAS_WIDTH as f32, 1.0 - (top + height) as f32 / atlasHeight as f32];
+            glyph.UVMax = [(left + width) as f32 / ATLAS_WIDTH as f32, 1.0 - top as f32 / atlasHeight as f32];
+
+            glyphs.insert(c, glyph);
+        }
+
+        let image = RawImage2d::from_raw_rgba(pixels, (ATLAS_WIDTH, atlasHeight));
+        let texture = Texture2d::new(display, image).unwrap();
+
+        let mut kerning = HashMap::new();
+
+        for a in glyphs.keys().filter(|x| x.is_ascii())
+        {
+            for b in glyphs.keys().filter(|x| x.is_ascii())
+            {
+                let amount = scaled.kern(font.glyph_id(*a), font.glyph_id(*b)).round();
+
+                if amount != 0.0
+                {
+                    kerning.insert((*a, *b), amount);
+                }
+            }
+        }
+
+        Self
+        {
+            Name: String::from(path.trim()),
+            Texture: Arc::new(texture),
+            LineHeight: (scaled.height() + scaled.line_gap()).ceil(),
+            Base: ascent.round(),
+            _glyphs: glyphs,
+            _kerning: kerning
+        }
+    }
+
+    fn EmptyGlyph(advance: f32) -> Glyph
+    {
+        Glyph
+        {
+            UVMin: [0.0, 0.0],
+            UVMax: [0.0, 0.0],
+            Size: (0.0, 0.0),
+            Offset: (0.0, 0.0),
+            Advance: advance
+        }
+    }
+
+    /// Glyph for a character. Falls back to the upper case letter, then '?'.
+    pub fn Glyph(&self, c: char) -> Option<&Glyph>
+    {
+        self._glyphs.get(&c)
+            .or_else(|| c.to_uppercase().next().and_then(|x| self._glyphs.get(&x)))
+            .or_else(|| self._glyphs.get(&'?'))
+    }
+
+    pub fn Kerning(&self, a: char, b: char) -> f32
+    {
+        *self._kerning.get(&(a, b)).unwrap_or(&0.0)
+    }
+
+    /// Width of a single line in font pixels.
+    fn LineWidth(&self, line: &str) -> f32
+    {
+        let mut width = 0.0;
+        let mut previous: Option<char> = None;
+
+        for c in line.chars()
+        {
+            if let Some(p) = previous
+            {
+                width += self.Kerning(p, c);
+            }
+
+            width += self.Glyph(c).map(|x| x.Advance).unwrap_or(0.0);
+            previous = Some(c);
+        }
+
+        width
+    }
+
+    /// Splits text into lines at new lines and, with a max width, between words.
+    /// Words longer than the max width are left on a line of their own.
+    fn WrapLines(&self, text: &str, maxWidth: Option<f32>) -> Vec<String>
+    {
+        let mut lines = Vec::new();
+
+        for paragraph in text.split('\n')
+        {
+            let maxWidth = match maxWidth
+            {
+                Some(x) => x,
+                None =>
+                    {
+                        lines.push(String::from(paragraph));
+                        continue;
+                    }
+            };
+
+            let mut line = String::new();
+
+            for word in paragraph.split(' ')
+            {
+                let candidate = if line.is_empty() { String::from(word) } else { format!("{} {}", line, word) };
+
+                if !line.is_empty() && self.LineWidth(candidate.as_str()) > maxWidth
+                {
+                    lines.push(line);
+                    line = String::from(word);
+                }
+                else
+                {
+                    line = candidate;
+                }
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Positions every glyph of text in font pixels relative to the top left of the text,
+    /// with y growing downwards. Alignment is around x = 0.
+    pub fn Layout(&self, text: &str, style: &TextStyle) -> Vec<PlacedGlyph>
+    {
+        let scale = style.Scale.max(0.001);
+        let maxWidth = style.MaxWidth.map(|x| x * PIXELS_PER_UNIT / scale);
+        let lineHeight = (self.LineHeight * style.LineSpacing).round();
+
+        let mut placed = Vec::new();
+
+        for (row, line) in self.WrapLines(text, maxWidth).iter().enumerate()
+        {
+            let width = self.LineWidth(line.as_str());
+
+            // Whole pixel starts keep centered text from landing between pixels.
+            let mut x = match style.Align
+            {
+                ETextAlign::Left => 0.0,
+                ETextAlign::Center => -(width * 0.5).floor(),
+                ETextAlign::Right => -width
+            };
+
+            let y = row as f32 * lineHeight;
+            let mut previous: Option<char> = None;
+
+            for c in line.chars()
+            {
+                if let Some(p) = previous
+                {
+                    x += self.Kerning(p, c);
+                }
+
+                if let Some(glyph) = self.Glyph(c)
+                {
+                    if glyph.Size.0 > 0.0 && glyph.Size.1 > 0.0
+                    {
+                        placed.push(PlacedGlyph { Glyph: *glyph, X: x + glyph.Offset.0, Y: y + glyph.Offset.1 });
+                    }
+
+                    x += glyph.Advance;
+                }
+
+                previous = Some(c);
+            }
+        }
+
+        placed
+    }
+
+    /// Width and height text takes up in world units.
+    pub fn Measure(&self, text: &str, style: &TextStyle) -> (f32, f32)
+    {
+        let scale = style.Scale.max(0.001);
+        let maxWidth = style.MaxWidth.map(|x| x * PIXELS_PER_UNIT / scale);
+        let lines = self.WrapLines(text, maxWidth);
+
+        let width = lines.iter().map(|x| self.LineWidth(x.as_str())).fold(0.0, f32::max);
+        let height = (self.LineHeight * style.LineSpacing).round() * (lines.len() as f32 - 1.0) + self.LineHeight;
+
+        (width * scale / PIXELS_PER_UNIT, height * scale / PIXELS_PER_UNIT)
+    }
+}
+
+/// Splits "key=value key2="quoted value"" into a map.
+fn BMFontValues(line: &str) -> HashMap<String, String>
+{
+    let mut values = HashMap::new();
+    let mut rest = line.trim();
+
+    while let Some(equals) = rest.find('=')
+    {
+        let key = rest[..equals].trim().to_string();
+        rest = &rest[equals + 1..];
+
+        let value;
+
+        if let Some(stripped) = rest.strip_prefix('"')
+        {
+            let end = stripped.find('"').unwrap_or(stripped.len());
+            value = stripped[..end].to_string();
+            rest = &stripped[(end + 1).min(stripped.len())..];
+        }
+        else
+        {
+            let end = rest.find(' ').unwrap_or(rest.len());
+            value = rest[..end].to_string();
+            rest = &rest[end..];
+        }
+
+        values.insert(key, value);
+        rest = rest.trim_start();
+    }
+
+    values
+}
+
+impl Renderer
+{
+    /// Draws text this frame.
+    ///
+    /// position - Top left of the text for left aligned text, top center or top right otherwise.
+    ///            On the UI layer this is in screen pixels from the bottom left,
+    ///            on every other layer it is a world position.
+    pub fn DrawText(&mut self, font: &Font, text: &str, position: Float3, style: &TextStyle)
+    {
+        let unit = style.Scale / PIXELS_PER_UNIT;
+
+        for placed in font.Layout(text, style)
+        {
+            let glyph = placed.Glyph;
+
+            let left = position.x() + placed.X * unit;
+            let top = position.y() - placed.Y * unit;
+            let right = left + glyph.Size.0 * unit;
+            let bottom = top - glyph.Size.1 * unit;
+
+            self.Submit(
+                SpriteQuad
+                {
+                    Texture: font.Texture.clone(),
+                    NormalMap: None,
+                    Shader: String::from(DEFAULT_FRAGMENT),
+                    Parameters: None,
+                    Corners:
+                    [
+                        Float3::new(left, bottom, position.z()),
+                        Float3::new(right, bottom, position.z()),
+                        Float3::new(right, top, position.z()),
+                        Float3::new(left, top, position.z()),
+                    ],
+                    UVMin: glyph.UVMin,
+                    UVMax: glyph.UVMax,
+                    Tint: style.Color,
+                    IsLit: false,
+                    ScrollFactor: Float3::one(),
+                    Layer: style.Layer,
+                    OrderInLayer: style.OrderInLayer
+                });
+        }
+    }
+}
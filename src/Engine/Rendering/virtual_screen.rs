@@ -0,0 +1,148 @@
+use glium::{BlitTarget, Display, Frame, Rect, Surface, Texture2d};
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::glutin::surface::WindowSurface;
+use glium::uniforms::MagnifySamplerFilter;
+
+/// Resolution the scene is drawn at before being scaled to the window.
+pub const DEFAULT_VIRTUAL_RESOLUTION: (u32, u32) = (320, 180);
+
+/// Fixed size offscreen target the scene is drawn into.
+///
+/// The image is scaled to the window by the largest whole number that fits,
+/// so every virtual pixel covers the same number of window pixels.
+/// Leftover space is filled with bars.
+pub struct VirtualScreen
+{
+    pub Width: u32,
+    pub Height: u32,
+
+    /// Color of the bars around the scaled image.
+    pub LetterboxColor: (f32, f32, f32, f32),
+
+    _sceneTexture: Option<Texture2d>,
+
+    /// Size of the window the scene was last presented to.
+    _windowSize: (u32, u32)
+}
+
+impl VirtualScreen
+{
+    pub fn Create(width: u32, height: u32) -> Self
+    {
+        Self
+        {
+            Width: width,
+            Height: height,
+            LetterboxColor: (0.0, 0.0, 0.0, 1.0),
+            _sceneTexture: None,
+            _windowSize: (width, height)
+        }
+    }
+
+    /// Records the window size, set by the game at the start of each frame.
+    pub fn SetWindowSize(&mut self, windowSize: (u32, u32))
+    {
+        self._windowSize = windowSize;
+    }
+
+    /// Converts a window position, origin top left as the mouse reports it,
+    /// to virtual pixels with the origin at the bottom left of the scene.
+    /// Positions over the letterbox fall outside of 0 to Width / Height.
+    pub fn WindowToVirtual(&self, windowPosition: (f64, f64)) -> (f32, f32)
+    {
+        let viewport = self.Viewport(self._windowSize);
+        let scale = viewport.width as f32 / self.Width as f32;
+
+        // Window positions grow downwards, the scene grows upwards.
+        let y = self._windowSize.1 as f32 - windowPosition.1 as f32;
+
+        (
+            (windowPosition.0 as f32 - viewport.left as f32) / scale,
+            (y - viewport.bottom as f32) / scale
+        )
+    }
+
+    /// Converts virtual pixels back to a window position, origin top left.
+    pub fn VirtualToWindow(&self, virtualPosition: (f32, f32)) -> (f64, f64)
+    {
+        let viewport = self.Viewport(self._windowSize);
+        let scale = viewport.width as f32 / self.Width as f32;
+
+        let y = viewport.bottom as f32 + virtualPosition.1 * scale;
+
+        (
+            (viewport.left as f32 + virtualPosition.0 * scale) as f64,
+            (self._windowSize.1 as f32 - y) as f64
+        )
+    }
+
+    /// Makes sure the scene texture matches the virtual resolution.
+    pub fn Prepare(&mut self, display: &Display<WindowSurface>)
+    {
+        let isStale = match &self._sceneTexture
+        {
+            Some(x) => x.dimensions() != (self.Width, self.Height),
+            None => true
+        };
+
+        if isStale
+        {
+            self._sceneTexture = Some(Texture2d::empty(display, self.Width, self.Height).unwrap());
+        }
+    }
+
+    /// Framebuffer the scene should be drawn into.
+    pub fn SceneTarget(&self, display: &Display<WindowSurface>) -> SimpleFrameBuffer<'_>
+    {
+        SimpleFrameBuffer::new(display, self.SceneTexture()).unwrap()
+    }
+
+    pub fn SceneTexture(&self) -> &Texture2d
+    {
+        self._sceneTexture.as_ref().unwrap()
+    }
+
+    /// Whole number of window pixels per virtual pixel, at least 1.
+    pub fn Scale(&self, windowSize: (u32, u32)) -> u32
+    {
+        (windowSize.0 / self.Width)
+            .min(windowSize.1 / self.Height)
+            .max(1)
+    }
+
+    /// Area of the window the scaled scene covers, centered.
+    pub fn Viewport(&self, windowSize: (u32, u32)) -> Rect
+    {
+        let scale = self.Scale(windowSize);
+        let width = self.Width * scale;
+        let height = self.Height * scale;
+
+        Rect
+        {
+            left: windowSize.0.saturating_sub(width) / 2,
+            bottom: windowSize.1.saturating_sub(height) / 2,
+            width,
+            height
+        }
+    }
+
+    /// Clears the window to the letterbox color and draws the scene scaled into the viewport.
+    pub fn Present(&self, target: &mut Frame)
+    {
+        let c = self.LetterboxColor;
+        target.clear_color(c.0, c.1, c.2, c.3);
+
+        let viewport = self.Viewport(target.get_dimensions());
+
+        self.SceneTexture().as_surface().blit_whole_color_to(
+            target,
+            &BlitTarget
+            {
+                left: viewport.left,
+                bottom: viewport.bottom,
+                width: viewport.width as i32,
+                height: viewport.height as i32
+            },
+            MagnifySamplerFilter::Nearest);
+    }
+}
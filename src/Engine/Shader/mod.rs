@@ -1,66 +1,508 @@
-use std::collections::hash_map::HashMap;
-use std::fs;
-
-// Directory Delimiter is OS dependent.
-// Need to make this delimiter OS independent
-pub const DEFAULT_FRAGMENT: &str = "Shaders\\F_UnlitSprite.shader";
-
-pub const DEFAULT_VERTEX: &str = "Shaders\\V_Standard.shader";
-
-/// Loads all shaders stored in the Shader folder and allows query for them.
-/// Avoids the needs for loading files on runtime.
-pub struct ShaderModule
-{
-    _programLookUp: HashMap<String, String>
-}
-
-
-impl ShaderModule
-{
-    /// Creates this ShaderModule and Loads all shaders in the Shaders folder.
-    pub fn Create() -> Self
-    {
-        let mut shaderTable = HashMap::<String,String>::new();
-
-        let shaderFileNames: Vec<String> = fs::read_dir("Shaders")
-            .unwrap()
-            .map(|x| x.unwrap().path())
-            .filter(|x| x.is_file())
-            .filter(|x| x.extension().unwrap() == "shader")
-            .map(|x| String::from(x.to_str().unwrap()))
-            .collect();
-
-        for shaderFile in &shaderFileNames 
-        {
-            let fileContents = fs::read_to_string(shaderFile).unwrap();
-            let name = String::from(shaderFile);
-
-            println!("Loaded Shader {}", name);
-
-            shaderTable.insert(name, fileContents);
-        }
-
-        Self
-        {
-            _programLookUp: shaderTable
-        }
-    }
-
-    pub fn GetShader(&self, shaderName: &str) -> String
-    {
-        let option = self._programLookUp.get(shaderName);
-
-        if option.is_none()
-        {
-            panic!("Could not find shader {}", shaderName);
-        }
-
-        // I would prefer avoiding copying the shader program.
-        String::from(option.unwrap())
-    }
-}
-
-
-
-
-
+use std::collections::hash_map::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+use glium::{Display, Program, ProgramCreationError};
+use glium::glutin::surface::WindowSurface;
+use glium::program::ShaderType;
+
+// Names are stored with '/' separators, lookups accept either separator.
+pub const DEFAULT_FRAGMENT: &str = "Shaders\\F_UnlitSprite.shader";
+
+pub const DEFAULT_VERTEX: &str = "Shaders\\V_Standard.shader";
+
+/// How often the Shaders folder is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Separates a shader file from its defines in a variant name.
+pub const VARIANT_SEPARATOR: char = '#';
+
+/// Folder shaders are loaded from, includes are relative to it.
+const SHADER_FOLDER: &str = "Shaders";
+
+/// Loads all shaders stored in the Shader folder and allows query for them.
+/// Avoids the needs for loading files on runtime.
+///
+/// Programs are compiled once per vertex and fragment pair and shared by every user.
+/// Files are watched while the game runs, a changed file is recompiled into every
+/// program using it. When that fails the previous program stays in use and the
+/// GLSL error is reported with its file and line.
+///
+/// Sources are preprocessed before compiling:
+///     #include "Include/lighting.glsl" - pastes a file from the Shaders folder, once per program.
+///     Variants - "Shaders/F_UnlitSprite.shader#LIT#OUTLINE" compiles the file with
+///         LIT and OUTLINE defined. NAME=VALUE defines a value. See Variant.
+pub struct ShaderModule
+{
+    _programLookUp: HashMap<String, String>,
+
+    /// Last modification time of every loaded file.
+    _modifiedTimes: HashMap<String, SystemTime>,
+
+    /// Compiled programs keyed by vertex and fragment shader variant name.
+    _programs: HashMap<(String, String), Program>,
+
+    /// Every file a program was built from, includes too.
+    _dependencies: HashMap<(String, String), Vec<String>>,
+
+    /// Pairs that never compiled, retried once one of their files changes.
+    _failed: HashSet<(String, String)>,
+
+    /// Pairs that compiled before but failed their last reload, still drawing with the old program.
+    _stale: HashSet<(String, String)>,
+
+    _lastError: Option<String>,
+    _lastPoll: Instant
+}
+
+/// Shader source with its includes pasted in and defines added.
+struct PreprocessedSource
+{
+    Text: String,
+
+    /// File and line each line of Text came from.
+    Lines: Vec<(String, u32)>,
+
+    /// Every file that was read.
+    Files: Vec<String>
+}
+
+
+impl ShaderModule
+{
+    /// Creates this ShaderModule and Loads all shaders in the Shaders folder.
+    pub fn Create() -> Self
+    {
+        let mut module = Self
+        {
+            _programLookUp: HashMap::new(),
+            _modifiedTimes: HashMap::new(),
+            _programs: HashMap::new(),
+            _dependencies: HashMap::new(),
+            _failed: HashSet::new(),
+            _stale: HashSet::new(),
+            _lastError: None,
+            _lastPoll: Instant::now()
+        };
+
+        for (shaderFile, modified) in Self::ShaderFiles()
+        {
+            let fileContents = fs::read_to_string(&shaderFile).unwrap();
+            let name = Self::Key(shaderFile.as_str());
+
+            println!("Loaded Shader {}", name);
+
+            module._programLookUp.insert(name.clone(), fileContents);
+            module._modifiedTimes.insert(name, modified);
+        }
+
+        module
+    }
+
+    /// Name of a shader compiled with the given defines, usable anywhere a shader name is.
+    /// Defines are sorted so the same set always shares one program.
+    pub fn Variant(shader: &str, defines: &[&str]) -> String
+    {
+        let (file, mut all) = Self::SplitVariant(shader);
+        all.extend(defines.iter().map(|x| String::from(x.trim())).filter(|x| !x.is_empty()));
+
+        all.sort();
+        all.dedup();
+
+        let mut name = file;
+
+        for define in all
+        {
+            name.push(VARIANT_SEPARATOR);
+            name.push_str(define.as_str());
+        }
+
+        name
+    }
+
+    /// Splits a variant name into its file and defines.
+    pub fn SplitVariant(shader: &str) -> (String, Vec<String>)
+    {
+        let mut parts = shader.split(VARIANT_SEPARATOR);
+        let file = Self::Key(parts.next().unwrap_or("").trim());
+        let defines = parts.map(|x| String::from(x.trim())).filter(|x| !x.is_empty()).collect();
+
+        (file, defines)
+    }
+
+    pub fn GetShader(&self, shaderName: &str) -> String
+    {
+        let option = self._programLookUp.get(&Self::Key(shaderName));
+
+        if option.is_none()
+        {
+            panic!("Could not find shader {}", shaderName);
+        }
+
+        // I would prefer avoiding copying the shader program.
+        String::from(option.unwrap())
+    }
+
+    /// Program for a vertex and fragment shader pair, compiled the first time it is asked for.
+    /// Either name can be a variant. None if it has never compiled, the error is reported once.
+    pub fn Program(&mut self, display: &Display<WindowSurface>, vertexShader: &str, fragmentShader: &str) -> Option<&Program>
+    {
+        let key = Self::ProgramKey(vertexShader, fragmentShader);
+
+        if !self._programs.contains_key(&key) && !self._failed.contains(&key)
+        {
+            match self.Compile(display, &key)
+            {
+                Ok(x) => { self._programs.insert(key.clone(), x); },
+                Err(x) =>
+                    {
+                        self.ReportError(x);
+                        self._failed.insert(key.clone());
+                    }
+            }
+        }
+
+        self._programs.get(&key)
+    }
+
+    /// Program that has already been compiled, i.e. to check a pair is usable.
+    pub fn GetProgram(&self, vertexShader: &str, fragmentShader: &str) -> Option<&Program>
+    {
+        self._programs.get(&Self::ProgramKey(vertexShader, fragmentShader))
+    }
+
+    /// Latest compile error, cleared once every changed program compiles again.
+    pub fn LastError(&self) -> Option<&str>
+    {
+        self._lastError.as_deref()
+    }
+
+    /// Checks the Shaders folder for changed or new files and recompiles every program using them.
+    pub fn Update(&mut self, display: &Display<WindowSurface>)
+    {
+        if self._lastPoll.elapsed() < POLL_INTERVAL
+        {
+            return;
+        }
+
+        self._lastPoll = Instant::now();
+
+        let mut changed: Vec<String> = Vec::new();
+
+        for (shaderFile, modified) in Self::ShaderFiles()
+        {
+            let name = Self::Key(shaderFile.as_str());
+
+            if self._modifiedTimes.get(&name) == Some(&modified)
+            {
+                continue;
+            }
+
+            // Editors can save in several steps, an unreadable file is tried again next poll.
+            let fileContents = match fs::read_to_string(&shaderFile)
+            {
+                Ok(x) => x,
+                Err(_) => continue
+            };
+
+            println!("Reloaded Shader {}", name);
+
+            self._programLookUp.insert(name.clone(), fileContents);
+            self._modifiedTimes.insert(name.clone(), modified);
+            changed.push(name);
+        }
+
+        if changed.is_empty()
+        {
+            return;
+        }
+
+        let isAffected = |key: &(String, String)|
+            {
+                match self._dependencies.get(key)
+                {
+                    Some(files) => files.iter().any(|x| changed.contains(x)),
+                    None => changed.contains(&Self::SplitVariant(&key.0).0) || changed.contains(&Self::SplitVariant(&key.1).0)
+                }
+            };
+
+        let mut keys: Vec<(String, String)> = self._programs.keys().filter(|x| isAffected(x)).cloned().collect();
+        keys.extend(self._failed.iter().filter(|x| isAffected(x)).cloned());
+
+        for key in keys
+        {
+            match self.Compile(display, &key)
+            {
+                Ok(x) =>
+                    {
+                        self._failed.remove(&key);
+                        self._stale.remove(&key);
+                        self._programs.insert(key, x);
+                    }
+
+                // The old program, if any, keeps drawing until the file is fixed.
+                Err(x) =>
+                    {
+                        self.ReportError(x);
+
+                        if self._programs.contains_key(&key)
+                        {
+                            self._stale.insert(key);
+                        }
+                    }
+            }
+        }
+
+        // Keep the error while any program is broken, including ones outside this reload.
+        if self._failed.is_empty() && self._stale.is_empty()
+        {
+            self._lastError = None;
+        }
+    }
+
+    fn Compile(&mut self, display: &Display<WindowSurface>, key: &(String, String)) -> Result<Program, String>
+    {
+        let vertex = self.Preprocess(&key.0);
+        let fragment = self.Preprocess(&key.1);
+
+        // Kept even on failure so fixing any of the files retries the program.
+        let mut files: Vec<String> = Vec::new();
+
+        for source in [&vertex, &fragment]
+        {
+            match source
+            {
+                Ok(x) => files.extend(x.Files.iter().cloned()),
+                Err((_, x)) => files.extend(x.iter().cloned())
+            }
+        }
+
+        self._dependencies.insert(key.clone(), files);
+
+        let vertex = vertex.map_err(|x| x.0)?;
+        let fragment = fragment.map_err(|x| x.0)?;
+
+        Program::from_source(display, vertex.Text.as_str(), fragment.Text.as_str(), None)
+            .map_err(|x| Self::FormatError(x, &vertex, &fragment))
+    }
+
+    /// Pastes in includes and adds the variant's defines after #version.
+    /// On failure returns the error and the files read so far.
+    fn Preprocess(&self, shader: &str) -> Result<PreprocessedSource, (String, Vec<String>)>
+    {
+        let (file, defines) = Self::SplitVariant(shader);
+
+        let mut source = PreprocessedSource
+        {
+            Text: String::new(),
+            Lines: Vec::new(),
+            Files: Vec::new()
+        };
+
+        let defineLines: Vec<String> = defines
+            .iter()
+            .map(|x| match x.split_once('=')
+            {
+                Some((name, value)) => format!("#define {} {}", name.trim(), value.trim()),
+                None => format!("#define {}", x)
+            })
+            .collect();
+
+        match self.Expand(&file, &defineLines, &mut source)
+        {
+            Ok(_) => Ok(source),
+            Err(x) => Err((x, source.Files))
+        }
+    }
+
+    fn Expand(&self, file: &String, defineLines: &Vec<String>, source: &mut PreprocessedSource) -> Result<(), String>
+    {
+        // Each file is pasted once per program, a second include is skipped.
+        if source.Files.contains(file)
+        {
+            return Ok(());
+        }
+
+        source.Files.push(file.clone());
+
+        let contents = match self._programLookUp.get(file)
+        {
+            Some(x) => x,
+            None => return Err(format!("{}: shader not found", file))
+        };
+
+        let isRoot = source.Files.len() == 1;
+        let hasVersion = contents.lines().any(|x| x.trim_start().starts_with("#version"));
+
+        // Without a #version line the defines go first.
+        if isRoot && !hasVersion
+        {
+            for define in defineLines
+            {
+                Self::PushLine(source, define, file, 0);
+            }
+        }
+
+        for (index, line) in contents.lines().enumerate()
+        {
+            let lineNumber = index as u32 + 1;
+            let trimmed = line.trim_start();
+
+            if let Some(include) = trimmed.strip_prefix("#include")
+            {
+                let path = include.trim().trim_matches(|x| x == '"' || x == '<' || x == '>');
+
+                if path.is_empty()
+                {
+                    return Err(format!("{}:{}: #include needs a file", file, lineNumber));
+                }
+
+                let included = Self::Key(Path::new(SHADER_FOLDER).join(path).to_str().unwrap());
+
+                if !self._programLookUp.contains_key(&included)
+                {
+                    return Err(format!("{}:{}: included file {} not found", file, lineNumber, included));
+                }
+
+                self.Expand(&included, defineLines, source)?;
+                continue;
+            }
+
+            Self::PushLine(source, line, file, lineNumber);
+
+            if isRoot && trimmed.starts_with("#version")
+            {
+                for define in defineLines
+                {
+                    Self::PushLine(source, define, file, lineNumber);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn PushLine(source: &mut PreprocessedSource, line: &str, file: &String, lineNumber: u32)
+    {
+        source.Text.push_str(line);
+        source.Text.push('\n');
+        source.Lines.push((file.clone(), lineNumber));
+    }
+
+    fn ReportError(&mut self, error: String)
+    {
+        println!("Shader Error:\n{}", error);
+        self._lastError = Some(error);
+    }
+
+    /// Prefixes each line of the GLSL log with the file and line it points at,
+    /// looking through includes back to the original file.
+    fn FormatError(error: ProgramCreationError, vertex: &PreprocessedSource, fragment: &PreprocessedSource) -> String
+    {
+        match error
+        {
+            ProgramCreationError::CompilationError(log, shaderType) =>
+                {
+                    let source = match shaderType
+                    {
+                        ShaderType::Vertex => vertex,
+                        _ => fragment
+                    };
+
+                    log.lines()
+                        .map(|x| x.trim())
+                        .filter(|x| !x.is_empty())
+                        .map(|x| match ErrorLine(x).and_then(|line| source.Lines.get(line.max(1) as usize - 1))
+                        {
+                            Some((file, line)) => format!("{}:{}: {}", file, line, x),
+                            None => format!("{}: {}", source.Files[0], x)
+                        })
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                }
+
+            ProgramCreationError::LinkingError(log) =>
+                format!("{} + {}: link error: {}", vertex.Files[0], fragment.Files[0], log.trim()),
+
+            x => format!("{} + {}: {}", vertex.Files[0], fragment.Files[0], x)
+        }
+    }
+
+    /// Every .shader and .glsl file in the Shaders folder and below, with its modification time.
+    fn ShaderFiles() -> Vec<(String, SystemTime)>
+    {
+        let mut files = Vec::new();
+        let mut folders = vec![Path::new(SHADER_FOLDER).to_path_buf()];
+
+        while let Some(folder) = folders.pop()
+        {
+            for path in fs::read_dir(&folder).unwrap().filter_map(|x| x.ok()).map(|x| x.path())
+            {
+                if path.is_dir()
+                {
+                    folders.push(path);
+                    continue;
+                }
+
+                let isShader = path
+                    .extension()
+                    .map_or(false, |x| x == "shader" || x == "glsl");
+
+                if !isShader
+                {
+                    continue;
+                }
+
+                let modified = fs::metadata(&path)
+                    .and_then(|m| m.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+
+                files.push((String::from(path.to_str().unwrap()), modified));
+            }
+        }
+
+        files
+    }
+
+    /// Names are used as given, build variants with Variant so each set of defines shares one program.
+    fn ProgramKey(vertexShader: &str, fragmentShader: &str) -> (String, String)
+    {
+        (Self::Key(vertexShader), Self::Key(fragmentShader))
+    }
+
+    fn Key(shaderName: &str) -> String
+    {
+        shaderName.replace('\\', "/")
+    }
+}
+
+/// Source line a GLSL log line refers to.
+/// Drivers write it as "0:12(5):", "ERROR: 0:12:" or "0(12) :", the leading 0 being the source string.
+fn ErrorLine(logLine: &str) -> Option<u32>
+{
+    let bytes = logLine.as_bytes();
+
+    for i in 0..bytes.len().saturating_sub(2)
+    {
+        let isStart = i == 0 || !bytes[i - 1].is_ascii_digit();
+
+        if !isStart || bytes[i] != b'0' || (bytes[i + 1] != b':' && bytes[i + 1] != b'(')
+        {
+            continue;
+        }
+
+        let digits: String = logLine[i + 2..]
+            .chars()
+            .take_while(|x| x.is_ascii_digit())
+            .collect();
+
+        if let Ok(line) = digits.parse()
+        {
+            return Some(line);
+        }
+    }
+
+    None
+}
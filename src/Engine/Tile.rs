@@ -1,8 +1,6 @@
 use std::fmt::{Debug, Formatter};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
-use glium::{Display, Frame};
-use glium::glutin::surface::WindowSurface;
 use uuid::Uuid;
 
 use crate::Engine::Components::Collider::Collider;
@@ -11,6 +9,7 @@ use crate::Engine::Frame::GameFrame;
 use crate::Engine::GameAPI::GameAPI;
 use crate::Engine::GameEntity::{EntityHeader, TEntity};
 use crate::Engine::Math::Float3;
+use crate::Engine::Rendering::{ESortingLayer, Renderer};
 
 pub struct Tile
 {
@@ -26,17 +25,28 @@ impl Tile
         position: Float3,
         sprite: Arc<Sprite>,
         isLit: bool,
-        display: &Display<WindowSurface>,
         collider: Option<Collider>
     ) -> Self
     {
         Self
         {
             Header: EntityHeader::Create(name, position),
-            _renderer: Renderer2D::New(display, sprite, isLit),
+            _renderer: Renderer2D::New(sprite, isLit),
             _collider: collider
         }
     }
+
+    /// Sets the layer the tile is drawn in and its order within that layer.
+    pub fn SetSortingLayer(&mut self, layer: ESortingLayer, orderInLayer: i32)
+    {
+        self._renderer.SetSortingLayer(layer, orderInLayer);
+    }
+
+    /// Sprite renderer, i.e. to change the tile's material.
+    pub fn Renderer(&mut self) -> &mut Renderer2D
+    {
+        &mut self._renderer
+    }
 }
 
 impl Debug for Tile {
@@ -60,7 +70,6 @@ impl TEntity for Tile
 
     unsafe fn Start(&mut self, api: *mut GameAPI)
     {
-        self._renderer.Start(&self.Header, api);
         match &mut self._collider
         {
             Some(T) =>
@@ -73,6 +82,8 @@ impl TEntity for Tile
 
     unsafe fn Update(&mut self, frame: &GameFrame, api: *mut GameAPI)
     {
+        self._renderer.Update(frame);
+
         match &mut self._collider
         {
             Some(T) =>
@@ -95,8 +106,8 @@ impl TEntity for Tile
         }
     }
 
-    fn Render(&mut self, frame: &GameFrame, target: &mut Frame)
+    fn Render(&mut self, _frame: &GameFrame, renderer: &mut Renderer)
     {
-        self._renderer.Render(&self.Header, frame, target);
+        self._renderer.Render(&self.Header, renderer);
     }
 }
\ No newline at end of file
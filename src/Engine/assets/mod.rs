@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use glium::{Display, Texture2d};
+use glium::glutin::surface::WindowSurface;
+
+use crate::Engine::Components::RenderComponents::Sprite;
+use crate::Engine::Rendering::material::Material;
+use crate::Engine::Rendering::text::Font;
+
+/// Loads assets once and shares them between every entity that uses them.
+///
+/// Assets are looked up by path and handed out as Arc handles.
+/// The manager holds one reference of its own, so an asset
+/// with a reference count of zero is only kept alive by the cache
+/// and can be unloaded.
+pub struct AssetManager
+{
+    _textures: HashMap<String, Arc<Texture2d>>,
+
+    /// Sprites keyed by path and sheet layout, since the same
+    /// texture can be split into cells in different ways.
+    _sprites: HashMap<String, Arc<Sprite>>,
+
+    /// Fonts keyed by path and rasterized size.
+    _fonts: HashMap<String, Arc<Font>>,
+
+    _materials: HashMap<String, Arc<Material>>
+}
+
+impl AssetManager
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            _textures: HashMap::new(),
+            _sprites: HashMap::new(),
+            _fonts: HashMap::new(),
+            _materials: HashMap::new()
+        }
+    }
+
+    /// Returns the texture at path, decoding and uploading it on first use.
+    pub fn Texture(&mut self, path: &str, display: &Display<WindowSurface>) -> Arc<Texture2d>
+    {
+        let key = path.trim();
+
+        if let Some(x) = self._textures.get(key)
+        {
+            return x.clone();
+        }
+
+        let texture = Arc::new(Sprite::LoadTexture(key, display));
+        self._textures.insert(String::from(key), texture.clone());
+
+        texture
+    }
+
+    /// Returns an animated sprite sharing the texture at path.
+    ///
+    /// path - Path to Sprite Image (png).
+    /// display - Display reference.
+    /// frameCount - total number of frames.
+    /// cellCounts - number of cell rows and columns.
+    /// animationSpeed - speed animation should play at.
+    pub fn Sprite(
+        &mut self,
+        path: &str,
+        display: &Display<WindowSurface>,
+        frameCount: u16,
+        cellCounts: (u16, u16),
+        animationSpeed: f32) -> Arc<Sprite>
+    {
+        let key = format!(
+            "{}|{}|{},{}|{}",
+            path.trim(), frameCount, cellCounts.0, cellCounts.1, animationSpeed);
+
+        if let Some(x) = self._sprites.get(&key)
+        {
+            return x.clone();
+        }
+
+        let texture = self.Texture(path, display);
+        let sprite = Sprite::FromTexture(texture, frameCount, cellCounts, animationSpeed);
+        self._sprites.insert(key, sprite.clone());
+
+        sprite
+    }
+
+    /// Returns an animated sprite lit with the normal map at normalMapPath.
+    /// Both images must share the same cell layout.
+    pub fn NormalMappedSprite(
+        &mut self,
+        path: &str,
+        normalMapPath: &str,
+        display: &Display<WindowSurface>,
+        frameCount: u16,
+        cellCounts: (u16, u16),
+        animationSpeed: f32) -> Arc<Sprite>
+    {
+        let key = format!(
+            "{}|{}|{},{}|{}|{}",
+            path.trim(), frameCount, cellCounts.0, cellCounts.1, animationSpeed, normalMapPath.trim());
+
+        if let Some(x) = self._sprites.get(&key)
+        {
+            return x.clone();
+        }
+
+        let texture = self.Texture(path, display);
+        let normalMap = self.Texture(normalMapPath, display);
+        let sprite = Sprite::FromTextures(texture, Some(normalMap), frameCount, cellCounts, animationSpeed);
+        self._sprites.insert(key, sprite.clone());
+
+        sprite
+    }
+
+    /// Returns a single frame sprite sharing the texture at path.
+    pub fn SimpleSprite(&mut self, path: &str, display: &Display<WindowSurface>) -> Arc<Sprite>
+    {
+        self.Sprite(path, display, 1, (1, 1), 1.0)
+    }
+
+    /// Returns a sprite built from every frame of a gif.
+    pub fn GifSprite(&mut self, path: &str, display: &Display<WindowSurface>) -> Arc<Sprite>
+    {
+        let key = format!("{}|gif", path.trim());
+
+        if let Some(x) = self._sprites.get(&key)
+        {
+            return x.clone();
+        }
+
+        let sprite = Sprite::new_gif(path.trim(), display);
+        self._sprites.insert(key, sprite.clone());
+
+        sprite
+    }
+
+    /// Returns the font at path, BMFont for .fnt files and TrueType otherwise.
+    /// pixelSize and antialiased only apply to TrueType fonts.
+    pub fn Font(&mut self, path: &str, pixelSize: f32, antialiased: bool, display: &Display<WindowSurface>) -> Arc<Font>
+    {
+        let key = format!("{}|{}|{}", path.trim(), pixelSize, antialiased);
+
+        if let Some(x) = self._fonts.get(&key)
+        {
+            return x.clone();
+        }
+
+        let font = Arc::new(Font::FromFile(path, pixelSize, antialiased, display));
+        self._fonts.insert(key, font.clone());
+
+        font
+    }
+
+    /// Returns the material at path, sprites copy it to override parameters.
+    pub fn Material(&mut self, path: &str, display: &Display<WindowSurface>) -> Arc<Material>
+    {
+        let key = path.trim();
+
+        if let Some(x) = self._materials.get(key)
+        {
+            return x.clone();
+        }
+
+        let contents = match fs::read_to_string(key)
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read material: {} \n {}", key, err)
+        };
+
+        let material = Arc::new(Material::Parse(key, contents.as_str(), display, self));
+        self._materials.insert(String::from(key), material.clone());
+
+        material
+    }
+
+    /// Number of handles to the texture at path held outside of the manager.
+    pub fn ReferenceCount(&self, path: &str) -> usize
+    {
+        match self._textures.get(path.trim())
+        {
+            Some(x) => Arc::strong_count(x) - 1,
+            None => 0
+        }
+    }
+
+    /// Number of textures currently loaded.
+    pub fn LoadedTextureCount(&self) -> usize
+    {
+        self._textures.len()
+    }
+
+    /// Drops every asset that is no longer referenced outside of the manager.
+    /// Returns the number of textures that were unloaded.
+    pub fn UnloadUnused(&mut self) -> usize
+    {
+        // Sprites hold textures, so they have to be released first.
+        self._sprites.retain(|_, x| Arc::strong_count(x) > 1);
+        self._fonts.retain(|_, x| Arc::strong_count(x) > 1);
+        self._materials.retain(|_, x| Arc::strong_count(x) > 1);
+
+        let before = self._textures.len();
+        self._textures.retain(|_, x| Arc::strong_count(x) > 1);
+
+        before - self._textures.len()
+    }
+}
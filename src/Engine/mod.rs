@@ -1,21 +1,25 @@
-pub mod Math;
-pub mod GameEntity;
-pub mod Frame;
-pub mod Components;
-pub mod SceneManager;
-pub mod SceneBuilder;
-pub mod Game;
-pub mod GameAPI;
-pub mod MetaInfo;
-pub mod GameState;
-pub mod Audio;
-pub mod Collision;
-pub mod Shader;
-
-pub mod Rendering;
-
-pub mod Editor;
-
-pub mod Tile;
-
-const DEBUG_MODE: bool = false;
+pub mod Math;
+pub mod GameEntity;
+pub mod Frame;
+pub mod Components;
+pub mod SceneManager;
+pub mod SceneBuilder;
+pub mod Game;
+pub mod GameAPI;
+pub mod MetaInfo;
+pub mod GameState;
+pub mod Audio;
+pub mod Collision;
+pub mod Shader;
+pub mod assets;
+pub mod ui;
+
+pub mod Rendering;
+
+pub mod Editor;
+
+pub mod Tile;
+
+/// Number of texture pixels that make up one world unit.
+/// Shared by sprite sizes, colliders and the camera so they stay in agreement.
+pub const PIXELS_PER_UNIT: f32 = 1.0;
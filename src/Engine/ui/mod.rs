@@ -0,0 +1,696 @@
+pub mod widget;
+
+use std::fs;
+
+use glium::Display;
+use glium::glutin::surface::WindowSurface;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Frame::GameFrame;
+use crate::Engine::Rendering::Renderer;
+use crate::Engine::Rendering::text::{ETextAlign, TextStyle};
+use crate::Engine::Rendering::virtual_screen::VirtualScreen;
+use crate::Engine::SceneBuilder::PROPERTY_SEPARATOR;
+use widget::*;
+
+/// Plain white texture used for flat colored panels and bars.
+pub const WHITE_TEXTURE: &str = "Assets/UI/white.png";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EUIEvent
+{
+    Activated,
+    FocusGained,
+    FocusLost
+}
+
+/// Something that happened to a widget during the last canvas update.
+#[derive(Clone, Debug)]
+pub struct UIEvent
+{
+    pub Name: String,
+    pub Kind: EUIEvent
+}
+
+/// Directions focus can be moved in.
+///
+/// Keyboard input is read by the canvas itself,
+/// any other device, i.e. a gamepad, drives the same focus through Navigate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ENavigation
+{
+    Up,
+    Down,
+    Left,
+    Right,
+
+    /// Next and previous focusable widget in tree order.
+    Next,
+    Previous,
+
+    /// Activates the focused widget.
+    Submit
+}
+
+/// Retained tree of screen space widgets, laid out against the virtual screen
+/// and drawn on the UI layer after the world.
+///
+/// Mouse hover and the arrow keys move a shared focus, Enter, Space or a click activates it.
+/// Game code reads what happened through WasActivated.
+pub struct UICanvas
+{
+    _widgets: Vec<Widget>,
+    _roots: Vec<WidgetId>,
+
+    _focused: Option<WidgetId>,
+
+    /// Widget the mouse went down on, it activates if the mouse is also released over it.
+    _pressed: Option<WidgetId>,
+
+    _lastMousePosition: (f32, f32),
+    _isPointerOverUI: bool,
+
+    _events: Vec<UIEvent>
+}
+
+impl UICanvas
+{
+    pub fn Create() -> Self
+    {
+        Self
+        {
+            _widgets: Vec::new(),
+            _roots: Vec::new(),
+            _focused: None,
+            _pressed: None,
+            _lastMousePosition: (0.0, 0.0),
+            _isPointerOverUI: false,
+            _events: Vec::new()
+        }
+    }
+
+    /// Adds a widget under a parent, or as a root laid out against the screen.
+    /// Children draw on top of their parent and of earlier siblings.
+    pub fn Add(&mut self, parent: Option<WidgetId>, mut widget: Widget) -> WidgetId
+    {
+        let id = self._widgets.len();
+        widget._parent = parent;
+
+        match parent
+        {
+            Some(x) => self._widgets[x]._children.push(id),
+            None => self._roots.push(id)
+        }
+
+        self._widgets.push(widget);
+        id
+    }
+
+    pub fn Find(&self, name: &str) -> Option<WidgetId>
+    {
+        self._widgets.iter().position(|x| x.Name == name)
+    }
+
+    pub fn WidgetMut(&mut self, id: WidgetId) -> &mut Widget
+    {
+        &mut self._widgets[id]
+    }
+
+    pub fn WidgetCount(&self) -> usize
+    {
+        self._widgets.len()
+    }
+
+    pub fn SetVisible(&mut self, name: &str, isVisible: bool)
+    {
+        match self.Find(name)
+        {
+            Some(x) => self._widgets[x].IsVisible = isVisible,
+            None => panic!("UI: no widget named {}", name)
+        }
+    }
+
+    /// A widget is shown only if it and every parent are visible.
+    pub fn IsVisibleInTree(&self, id: WidgetId) -> bool
+    {
+        let mut current = Some(id);
+
+        while let Some(x) = current
+        {
+            if !self._widgets[x].IsVisible
+            {
+                return false;
+            }
+
+            current = self._widgets[x]._parent;
+        }
+
+        true
+    }
+
+    pub fn Focused(&self) -> Option<WidgetId>
+    {
+        self._focused
+    }
+
+    /// Moves focus, raising FocusLost and FocusGained events.
+    pub fn Focus(&mut self, id: Option<WidgetId>)
+    {
+        if self._focused == id
+        {
+            return;
+        }
+
+        if let Some(x) = self._focused
+        {
+            self.PushEvent(x, EUIEvent::FocusLost);
+        }
+
+        self._focused = id;
+
+        if let Some(x) = id
+        {
+            self.PushEvent(x, EUIEvent::FocusGained);
+        }
+    }
+
+    pub fn WasActivated(&self, name: &str) -> bool
+    {
+        self._events
+            .iter()
+            .any(|x| x.Kind == EUIEvent::Activated && x.Name == name)
+    }
+
+    /// True while the mouse is over a visible widget that draws something,
+    /// so world input such as the editor camera can ignore clicks on the UI.
+    pub fn IsPointerOverUI(&self) -> bool
+    {
+        self._isPointerOverUI
+    }
+
+    /// Activates a widget as if it was clicked, running its action.
+    pub fn Activate(&mut self, id: WidgetId)
+    {
+        let action = match &self._widgets[id].Kind
+        {
+            EWidget::Button(x) if x.IsEnabled => x.Action.clone(),
+            EWidget::Button(_) => return,
+            _ => None
+        };
+
+        self.PushEvent(id, EUIEvent::Activated);
+
+        if let Some(x) = action
+        {
+            self.RunAction(x.as_str());
+        }
+    }
+
+    /// Runs canvas commands separated by ';': Show, Hide or Toggle a widget, or Focus a button.
+    pub fn RunAction(&mut self, action: &str)
+    {
+        for command in action.split(';').map(|x| x.trim()).filter(|x| !x.is_empty())
+        {
+            let (verb, target) = match command.split_once(' ')
+            {
+                Some((verb, target)) => (verb.trim(), target.trim()),
+                None => panic!("UI action needs a widget: {}", command)
+            };
+
+            let id = match self.Find(target)
+            {
+                Some(x) => x,
+                None => panic!("UI action {}: no widget named {}", command, target)
+            };
+
+            match verb.to_lowercase().as_str()
+            {
+                "show" => self._widgets[id].IsVisible = true,
+                "hide" => self._widgets[id].IsVisible = false,
+                "toggle" => self._widgets[id].IsVisible = !self._widgets[id].IsVisible,
+                "focus" => self.Focus(Some(id)),
+                x => panic!("Unknown UI action: {}", x)
+            }
+        }
+    }
+
+    /// Moves focus to the nearest visible focusable widget in a direction.
+    pub fn Navigate(&mut self, direction: ENavigation)
+    {
+        let focusable: Vec<WidgetId> = self.DrawOrder()
+            .into_iter()
+            .filter(|x| self._widgets[*x].IsFocusable())
+            .collect();
+
+        if focusable.is_empty()
+        {
+            return;
+        }
+
+        let current = match self._focused.filter(|x| focusable.contains(x))
+        {
+            Some(x) => x,
+
+            // Whatever is pressed first only picks a starting point.
+            None =>
+                {
+                    self.Focus(Some(focusable[0]));
+                    return;
+                }
+        };
+
+        let next = match direction
+        {
+            ENavigation::Submit =>
+                {
+                    self.Activate(current);
+                    return;
+                }
+
+            ENavigation::Next | ENavigation::Previous =>
+                {
+                    let index = focusable.iter().position(|x| *x == current).unwrap();
+                    let count = focusable.len();
+
+                    let index = if direction == ENavigation::Next { (index + 1) % count } else { (index + count - 1) % count };
+                    Some(focusable[index])
+                }
+
+            _ => self.NearestInDirection(current, &focusable, direction)
+        };
+
+        if next.is_some()
+        {
+            self.Focus(next);
+        }
+    }
+
+    /// Lays the tree out, then handles mouse and keyboard.
+    /// Events from the previous update are cleared first.
+    pub fn Update(&mut self, frame: &GameFrame, screen: &VirtualScreen)
+    {
+        self._events.clear();
+        self.Layout((screen.Width as f32, screen.Height as f32));
+
+        let deltaSeconds = frame.GameDeltaTime_Milliseconds() / 1000.0;
+
+        for widget in self._widgets.iter_mut()
+        {
+            if let EWidget::HealthBar(x) = &mut widget.Kind
+            {
+                x.Update(deltaSeconds);
+            }
+        }
+
+        // Hidden widgets cannot keep focus.
+        if let Some(x) = self._focused
+        {
+            if !self.IsVisibleInTree(x) || !self._widgets[x].IsFocusable()
+            {
+                self.Focus(None);
+            }
+        }
+
+        self.UpdatePointer(frame, screen);
+        self.UpdateKeyboard(frame);
+    }
+
+    /// Submits every visible widget to the UI layer, parents before their children.
+    pub fn Render(&self, renderer: &mut Renderer)
+    {
+        let mut orderInLayer = 0;
+
+        for id in self.DrawOrder()
+        {
+            let widget = &self._widgets[id];
+
+            let state = if self._pressed == Some(id) && self._focused == Some(id)
+            {
+                EWidgetState::Pressed
+            }
+            else if self._focused == Some(id)
+            {
+                EWidgetState::Highlighted
+            }
+            else
+            {
+                EWidgetState::Normal
+            };
+
+            orderInLayer = widget.Render(state, orderInLayer, renderer);
+        }
+    }
+
+    /// Reads a layout file and adds its widgets, see UI/menu.ui for the format.
+    pub fn Load(&mut self, path: &str, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        let contents = match fs::read_to_string(path)
+        {
+            Ok(x) => x,
+            Err(err) => panic!("Failed to read UI layout: {} \n {}", path, err)
+        };
+
+        let entries = contents
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty() && !x.starts_with("//"));
+
+        for entry in entries
+        {
+            let tokens: Vec<&str> = entry
+                .split(PROPERTY_SEPARATOR)
+                .map(|x| x.trim())
+                .collect();
+
+            if tokens[0] == "Hidden"
+            {
+                self.SetVisible(tokens[1], false);
+                continue;
+            }
+
+            if tokens[0] == "Focus"
+            {
+                let id = self.Find(tokens[1]);
+                self.Focus(id);
+                continue;
+            }
+
+            if tokens.len() < 8
+            {
+                panic!("{}: {} is missing properties", path, entry);
+            }
+
+            let name = tokens[1];
+
+            let parent = match tokens[2]
+            {
+                "None" => None,
+                x => match self.Find(x)
+                {
+                    Some(id) => Some(id),
+                    None => panic!("{}: parent {} of {} must be defined first", path, x, name)
+                }
+            };
+
+            let transform = RectTransform
+            {
+                AnchorMin: ParsePair(tokens[3]),
+                AnchorMax: ParsePair(tokens[4]),
+                Pivot: ParsePair(tokens[5]),
+                Position: ParsePair(tokens[6]),
+                Size: ParsePair(tokens[7])
+            };
+
+            let property = |index: usize| -> &str
+                {
+                    match tokens.get(index)
+                    {
+                        Some(x) => x,
+                        None => panic!("{}: {} is missing properties", path, name)
+                    }
+                };
+
+            let kind = match tokens[0]
+            {
+                "Empty" => EWidget::Empty,
+
+                "Panel" => EWidget::Panel
+                {
+                    Texture: assets.Texture(WHITE_TEXTURE, display),
+                    Color: ParseColor(property(8))
+                },
+
+                "Image" => EWidget::Image
+                {
+                    Texture: assets.Texture(property(8), display),
+                    Color: ParseColor(property(9)),
+                    PreserveAspect: tokens.get(10).map_or(false, |x| x.parse().unwrap())
+                },
+
+                "NineSlice" => EWidget::NineSlice(
+                    NineSlice
+                    {
+                        Texture: assets.Texture(property(8), display),
+                        Border: ParseBorder(property(9)),
+                        Color: ParseColor(property(10))
+                    }),
+
+                "Label" =>
+                    {
+                        let pixelSize: f32 = property(9).parse().unwrap();
+                        let mut style = TextStyle::Create();
+                        style.Color = ParseColor(property(11));
+                        style.Align = ParseAlign(property(12));
+
+                        EWidget::Label(UIText::New(assets.Font(property(8), pixelSize, false, display), property(10), style))
+                    }
+
+                "Button" =>
+                    {
+                        let pixelSize: f32 = property(11).parse().unwrap();
+                        let mut style = TextStyle::Create();
+                        style.Align = ETextAlign::Center;
+
+                        EWidget::Button(
+                            Button
+                            {
+                                Background: NineSlice
+                                {
+                                    Texture: assets.Texture(property(8), display),
+                                    Border: ParseBorder(property(9)),
+                                    Color: [1.0, 1.0, 1.0, 1.0]
+                                },
+                                Label: UIText::New(assets.Font(property(10), pixelSize, false, display), property(12), style),
+                                Action: tokens.get(13).filter(|x| **x != "None").map(|x| String::from(*x)),
+                                HighlightTint: [1.0, 0.85, 0.4, 1.0],
+                                PressedTint: [0.7, 0.6, 0.35, 1.0],
+                                IsEnabled: true
+                            })
+                    }
+
+                "HealthBar" =>
+                    {
+                        let mut bar = HealthBar::New(assets.Texture(WHITE_TEXTURE, display), property(8).parse().unwrap());
+
+                        if let Some(x) = tokens.get(9) { bar.FillColor = ParseColor(x); }
+                        if let Some(x) = tokens.get(10) { bar.BackColor = ParseColor(x); }
+
+                        EWidget::HealthBar(bar)
+                    }
+
+                x => panic!("{}: unknown widget type {}", path, x)
+            };
+
+            self.Add(parent, Widget::Create(name, transform, kind));
+        }
+    }
+
+    /// Resolves every rect against its parent, roots against the whole virtual screen.
+    fn Layout(&mut self, screenSize: (f32, f32))
+    {
+        let screen = UIRect::New(0.0, 0.0, screenSize.0, screenSize.1);
+        let mut stack: Vec<(WidgetId, UIRect)> = self._roots.iter().rev().map(|x| (*x, screen)).collect();
+
+        while let Some((id, parentRect)) = stack.pop()
+        {
+            let rect = self._widgets[id].Transform.Resolve(&parentRect);
+            self._widgets[id]._rect = rect;
+
+            for child in self._widgets[id]._children.iter().rev()
+            {
+                stack.push((*child, rect));
+            }
+        }
+    }
+
+    /// Visible widgets, depth first, parents before children.
+    fn DrawOrder(&self) -> Vec<WidgetId>
+    {
+        let mut order = Vec::new();
+        let mut stack: Vec<WidgetId> = self._roots.iter().rev().copied().collect();
+
+        while let Some(id) = stack.pop()
+        {
+            if !self._widgets[id].IsVisible
+            {
+                continue;
+            }
+
+            order.push(id);
+            stack.extend(self._widgets[id]._children.iter().rev());
+        }
+
+        order
+    }
+
+    fn UpdatePointer(&mut self, frame: &GameFrame, screen: &VirtualScreen)
+    {
+        let mouse = screen.WindowToVirtual(frame.Input.MousePosition());
+        let hasMoved = mouse != self._lastMousePosition;
+        self._lastMousePosition = mouse;
+
+        let order = self.DrawOrder();
+
+        self._isPointerOverUI = order
+            .iter()
+            .any(|x| !matches!(self._widgets[*x].Kind, EWidget::Empty) && self._widgets[*x]._rect.Contains(mouse));
+
+        // Topmost focusable widget under the mouse.
+        let hovered = order
+            .into_iter()
+            .rev()
+            .find(|x| self._widgets[*x].IsFocusable() && self._widgets[*x]._rect.Contains(mouse));
+
+        // Only moving the mouse steals focus, so a resting cursor doesn't fight the keyboard.
+        if hasMoved && hovered.is_some()
+        {
+            self.Focus(hovered);
+        }
+
+        if frame.Input.IsMousePressed(MouseButton::Left)
+        {
+            self._pressed = hovered;
+
+            if hovered.is_some()
+            {
+                self.Focus(hovered);
+            }
+        }
+
+        if frame.Input.IsMouseReleased(MouseButton::Left)
+        {
+            if let Some(x) = self._pressed.take()
+            {
+                if hovered == Some(x)
+                {
+                    self.Activate(x);
+                }
+            }
+        }
+    }
+
+    fn UpdateKeyboard(&mut self, frame: &GameFrame)
+    {
+        let isShiftDown = frame.Input.IsKeyDown(KeyCode::ShiftLeft) || frame.Input.IsKeyDown(KeyCode::ShiftRight);
+
+        let bindings =
+        [
+            (KeyCode::ArrowUp, ENavigation::Up),
+            (KeyCode::ArrowDown, ENavigation::Down),
+            (KeyCode::ArrowLeft, ENavigation::Left),
+            (KeyCode::ArrowRight, ENavigation::Right),
+            (KeyCode::Tab, if isShiftDown { ENavigation::Previous } else { ENavigation::Next }),
+            (KeyCode::Enter, ENavigation::Submit),
+            (KeyCode::Space, ENavigation::Submit)
+        ];
+
+        for (key, direction) in bindings
+        {
+            if frame.Input.IsKeyPressed(key)
+            {
+                self.Navigate(direction);
+            }
+        }
+    }
+
+    /// Closest candidate whose center lies in the direction,
+    /// favouring widgets in line with the current one over diagonal ones.
+    fn NearestInDirection(&self, current: WidgetId, candidates: &Vec<WidgetId>, direction: ENavigation) -> Option<WidgetId>
+    {
+        let from = self._widgets[current]._rect.Center();
+
+        candidates
+            .iter()
+            .filter(|x| **x != current)
+            .filter_map(|x|
+                {
+                    let to = self._widgets[*x]._rect.Center();
+                    let dx = to.0 - from.0;
+                    let dy = to.1 - from.1;
+
+                    let (along, across) = match direction
+                    {
+                        ENavigation::Up => (dy, dx),
+                        ENavigation::Down => (-dy, dx),
+                        ENavigation::Right => (dx, dy),
+                        ENavigation::Left => (-dx, dy),
+                        _ => return None
+                    };
+
+                    if along <= 0.0
+                    {
+                        return None;
+                    }
+
+                    Some((*x, along + across.abs() * 2.0))
+                })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|x| x.0)
+    }
+
+    fn PushEvent(&mut self, id: WidgetId, kind: EUIEvent)
+    {
+        self._events.push(
+            UIEvent
+            {
+                Name: self._widgets[id].Name.clone(),
+                Kind: kind
+            });
+    }
+}
+
+fn ParseValues(value: &str) -> Vec<f32>
+{
+    value.split(',').map(|x| x.trim().parse().unwrap()).collect()
+}
+
+fn ParsePair(value: &str) -> (f32, f32)
+{
+    let values = ParseValues(value);
+
+    if values.len() != 2
+    {
+        panic!("UI value needs x, y: {}", value);
+    }
+
+    (values[0], values[1])
+}
+
+/// Accepts r, g, b or r, g, b, a.
+fn ParseColor(value: &str) -> [f32; 4]
+{
+    let values = ParseValues(value);
+
+    match values.len()
+    {
+        3 => [values[0], values[1], values[2], 1.0],
+        4 => [values[0], values[1], values[2], values[3]],
+        _ => panic!("UI color needs r, g, b[, a]: {}", value)
+    }
+}
+
+/// A single value for every side, or left, right, bottom, top.
+fn ParseBorder(value: &str) -> [f32; 4]
+{
+    let values = ParseValues(value);
+
+    match values.len()
+    {
+        1 => [values[0]; 4],
+        4 => [values[0], values[1], values[2], values[3]],
+        _ => panic!("UI border needs one value or left, right, bottom, top: {}", value)
+    }
+}
+
+fn ParseAlign(value: &str) -> ETextAlign
+{
+    match value.to_lowercase().as_str()
+    {
+        "left" => ETextAlign::Left,
+        "center" => ETextAlign::Center,
+        "right" => ETextAlign::Right,
+        x => panic!("Unknown text alignment: {}", x)
+    }
+}
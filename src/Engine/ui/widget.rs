@@ -0,0 +1,465 @@
+use std::sync::Arc;
+
+use glium::Texture2d;
+
+use crate::Engine::Math::Float3;
+use crate::Engine::PIXELS_PER_UNIT;
+use crate::Engine::Rendering::{ESortingLayer, Renderer, SpriteQuad};
+use crate::Engine::Rendering::text::{ETextAlign, Font, TextStyle};
+use crate::Engine::Shader::DEFAULT_FRAGMENT;
+
+/// Index of a widget in its canvas.
+pub type WidgetId = usize;
+
+/// Rectangle in virtual screen pixels, origin at the bottom-left.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UIRect
+{
+    pub X: f32,
+    pub Y: f32,
+    pub Width: f32,
+    pub Height: f32
+}
+
+impl UIRect
+{
+    pub fn New(x: f32, y: f32, width: f32, height: f32) -> Self
+    {
+        Self
+        {
+            X: x,
+            Y: y,
+            Width: width,
+            Height: height
+        }
+    }
+
+    pub fn Contains(&self, point: (f32, f32)) -> bool
+    {
+        point.0 >= self.X && point.0 < self.X + self.Width &&
+            point.1 >= self.Y && point.1 < self.Y + self.Height
+    }
+
+    pub fn Center(&self) -> (f32, f32)
+    {
+        (self.X + self.Width * 0.5, self.Y + self.Height * 0.5)
+    }
+}
+
+/// Places a widget inside its parent's rect.
+///
+/// Anchors are normalized points in the parent, (0, 0) bottom-left to (1, 1) top-right.
+/// When min and max match on an axis the widget keeps its Size there,
+/// when they differ it stretches with the parent and Size is added on top, so negative values inset it.
+/// Pivot is the normalized point of the widget that sits on the anchors, moved by Position.
+#[derive(Copy, Clone, Debug)]
+pub struct RectTransform
+{
+    pub AnchorMin: (f32, f32),
+    pub AnchorMax: (f32, f32),
+    pub Pivot: (f32, f32),
+
+    /// Offset of the pivot from the anchors, in pixels.
+    pub Position: (f32, f32),
+
+    /// Size in pixels, or size added to the stretched size.
+    pub Size: (f32, f32)
+}
+
+impl RectTransform
+{
+    /// Rect of the widget inside the parent, snapped to whole pixels.
+    pub fn Resolve(&self, parent: &UIRect) -> UIRect
+    {
+        let (x, width) = Self::ResolveAxis(
+            parent.X, parent.Width, self.AnchorMin.0, self.AnchorMax.0, self.Pivot.0, self.Position.0, self.Size.0);
+
+        let (y, height) = Self::ResolveAxis(
+            parent.Y, parent.Height, self.AnchorMin.1, self.AnchorMax.1, self.Pivot.1, self.Position.1, self.Size.1);
+
+        UIRect::New(x.round(), y.round(), width.round().max(0.0), height.round().max(0.0))
+    }
+
+    fn ResolveAxis(parentStart: f32, parentSize: f32, anchorMin: f32, anchorMax: f32, pivot: f32, position: f32, size: f32) -> (f32, f32)
+    {
+        let start = parentStart + parentSize * anchorMin;
+        let end = parentStart + parentSize * anchorMax;
+
+        let length = (end - start) + size;
+        let pivotPoint = start + (end - start) * pivot + position;
+
+        (pivotPoint - length * pivot, length)
+    }
+}
+
+/// How a widget looks this frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EWidgetState
+{
+    Normal,
+
+    /// Focused by navigation or under the mouse.
+    Highlighted,
+    Pressed
+}
+
+/// Texture drawn with fixed size corners and edges that stretch to fill the rect.
+pub struct NineSlice
+{
+    pub Texture: Arc<Texture2d>,
+
+    /// Left, right, bottom and top borders in texture pixels.
+    pub Border: [f32; 4],
+    pub Color: [f32; 4]
+}
+
+/// Text placed inside a widget's rect, vertically centered and aligned by its style.
+pub struct UIText
+{
+    pub Font: Arc<Font>,
+    pub Text: String,
+    pub Style: TextStyle
+}
+
+pub struct Button
+{
+    pub Background: NineSlice,
+    pub Label: UIText,
+
+    /// Canvas commands run when the button is activated, i.e. "Hide MainMenu; Show HUD".
+    pub Action: Option<String>,
+
+    pub HighlightTint: [f32; 4],
+    pub PressedTint: [f32; 4],
+    pub IsEnabled: bool
+}
+
+/// Horizontal bar that fills from the left, with a trail that drains behind the fill after damage.
+pub struct HealthBar
+{
+    /// Plain white texture tinted for each part.
+    pub Texture: Arc<Texture2d>,
+
+    /// Fill amount between 0 and 1.
+    pub Value: f32,
+
+    pub FillColor: [f32; 4],
+    pub TrailColor: [f32; 4],
+    pub BackColor: [f32; 4],
+
+    /// Fraction of the bar the trail drains per second.
+    pub TrailSpeed: f32,
+
+    _trailValue: f32
+}
+
+impl HealthBar
+{
+    pub fn New(texture: Arc<Texture2d>, value: f32) -> Self
+    {
+        Self
+        {
+            Texture: texture,
+            Value: value.clamp(0.0, 1.0),
+            FillColor: [0.85, 0.15, 0.2, 1.0],
+            TrailColor: [1.0, 0.9, 0.6, 1.0],
+            BackColor: [0.1, 0.05, 0.1, 0.8],
+            TrailSpeed: 0.5,
+            _trailValue: value.clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn SetValue(&mut self, value: f32)
+    {
+        self.Value = value.clamp(0.0, 1.0);
+    }
+
+    /// Healing snaps the trail up, damage leaves it to drain.
+    pub fn Update(&mut self, deltaSeconds: f32)
+    {
+        if self._trailValue <= self.Value
+        {
+            self._trailValue = self.Value;
+            return;
+        }
+
+        self._trailValue = (self._trailValue - self.TrailSpeed * deltaSeconds).max(self.Value);
+    }
+}
+
+pub enum EWidget
+{
+    /// Groups and positions its children, draws nothing.
+    Empty,
+
+    /// Flat colored rect, the texture is plain white.
+    Panel { Texture: Arc<Texture2d>, Color: [f32; 4] },
+
+    /// Texture stretched over the rect, or fitted inside it keeping its aspect ratio.
+    Image { Texture: Arc<Texture2d>, Color: [f32; 4], PreserveAspect: bool },
+
+    NineSlice(NineSlice),
+    Label(UIText),
+    Button(Button),
+    HealthBar(HealthBar)
+}
+
+/// Node of a canvas' widget tree.
+pub struct Widget
+{
+    pub Name: String,
+    pub Transform: RectTransform,
+    pub Kind: EWidget,
+
+    /// Hidden widgets hide their children too.
+    pub IsVisible: bool,
+
+    pub(super) _parent: Option<WidgetId>,
+    pub(super) _children: Vec<WidgetId>,
+
+    /// Screen rect from the last layout.
+    pub(super) _rect: UIRect
+}
+
+impl Widget
+{
+    pub fn Create(name: &str, transform: RectTransform, kind: EWidget) -> Self
+    {
+        Self
+        {
+            Name: String::from(name),
+            Transform: transform,
+            Kind: kind,
+            IsVisible: true,
+            _parent: None,
+            _children: Vec::new(),
+            _rect: UIRect::New(0.0, 0.0, 0.0, 0.0)
+        }
+    }
+
+    /// Only enabled buttons take focus and activate.
+    pub fn IsFocusable(&self) -> bool
+    {
+        match &self.Kind
+        {
+            EWidget::Button(x) => x.IsEnabled,
+            _ => false
+        }
+    }
+
+    /// Submits the widget on the UI layer. Returns the next free order in layer.
+    pub(super) fn Render(&self, state: EWidgetState, orderInLayer: i32, renderer: &mut Renderer) -> i32
+    {
+        let rect = self._rect;
+
+        match &self.Kind
+        {
+            EWidget::Empty => return orderInLayer,
+
+            EWidget::Panel { Texture, Color } =>
+                SubmitRect(renderer, Texture, rect, [0.0, 0.0], [1.0, 1.0], *Color, orderInLayer),
+
+            EWidget::Image { Texture, Color, PreserveAspect } =>
+                {
+                    let fitted = if *PreserveAspect { FitAspect(rect, Texture) } else { rect };
+                    SubmitRect(renderer, Texture, fitted, [0.0, 0.0], [1.0, 1.0], *Color, orderInLayer);
+                }
+
+            EWidget::NineSlice(x) => x.Render(rect, x.Color, orderInLayer, renderer),
+
+            EWidget::Label(x) => x.Render(rect, orderInLayer, renderer),
+
+            EWidget::Button(x) =>
+                {
+                    let tint = match state
+                    {
+                        _ if !x.IsEnabled => [0.5, 0.5, 0.5, 1.0],
+                        EWidgetState::Normal => [1.0, 1.0, 1.0, 1.0],
+                        EWidgetState::Highlighted => x.HighlightTint,
+                        EWidgetState::Pressed => x.PressedTint
+                    };
+
+                    let color = MultiplyColor(x.Background.Color, tint);
+                    x.Background.Render(rect, color, orderInLayer, renderer);
+
+                    // Pressed buttons push their label down a pixel.
+                    let mut labelRect = rect;
+                    if state == EWidgetState::Pressed
+                    {
+                        labelRect.Y -= 1.0;
+                    }
+
+                    x.Label.Render(labelRect, orderInLayer + 1, renderer);
+                    return orderInLayer + 2;
+                }
+
+            EWidget::HealthBar(x) =>
+                {
+                    let mut trail = rect;
+                    trail.Width = (rect.Width * x._trailValue).round();
+
+                    let mut fill = rect;
+                    fill.Width = (rect.Width * x.Value).round();
+
+                    SubmitRect(renderer, &x.Texture, rect, [0.0, 0.0], [1.0, 1.0], x.BackColor, orderInLayer);
+                    SubmitRect(renderer, &x.Texture, trail, [0.0, 0.0], [1.0, 1.0], x.TrailColor, orderInLayer + 1);
+                    SubmitRect(renderer, &x.Texture, fill, [0.0, 0.0], [1.0, 1.0], x.FillColor, orderInLayer + 2);
+                    return orderInLayer + 3;
+                }
+        }
+
+        orderInLayer + 1
+    }
+}
+
+impl NineSlice
+{
+    fn Render(&self, rect: UIRect, color: [f32; 4], orderInLayer: i32, renderer: &mut Renderer)
+    {
+        let textureWidth = self.Texture.width() as f32;
+        let textureHeight = self.Texture.height() as f32;
+
+        // Borders shrink together when the rect is too small to fit them.
+        let scaleX = (rect.Width / (self.Border[0] + self.Border[1]).max(1.0)).min(1.0);
+        let scaleY = (rect.Height / (self.Border[2] + self.Border[3]).max(1.0)).min(1.0);
+
+        let xs =
+        [
+            rect.X,
+            rect.X + self.Border[0] * scaleX,
+            rect.X + rect.Width - self.Border[1] * scaleX,
+            rect.X + rect.Width
+        ];
+
+        let ys =
+        [
+            rect.Y,
+            rect.Y + self.Border[2] * scaleY,
+            rect.Y + rect.Height - self.Border[3] * scaleY,
+            rect.Y + rect.Height
+        ];
+
+        let us = [0.0, self.Border[0] / textureWidth, 1.0 - self.Border[1] / textureWidth, 1.0];
+        let vs = [0.0, self.Border[2] / textureHeight, 1.0 - self.Border[3] / textureHeight, 1.0];
+
+        for row in 0..3
+        {
+            for column in 0..3
+            {
+                let cell = UIRect::New(xs[column], ys[row], xs[column + 1] - xs[column], ys[row + 1] - ys[row]);
+
+                if cell.Width <= 0.0 || cell.Height <= 0.0
+                {
+                    continue;
+                }
+
+                SubmitRect(
+                    renderer,
+                    &self.Texture,
+                    cell,
+                    [us[column], vs[row]],
+                    [us[column + 1], vs[row + 1]],
+                    color,
+                    orderInLayer);
+            }
+        }
+    }
+}
+
+impl UIText
+{
+    pub fn New(font: Arc<Font>, text: &str, style: TextStyle) -> Self
+    {
+        Self
+        {
+            Font: font,
+            Text: String::from(text),
+            Style: style
+        }
+    }
+
+    fn Render(&self, rect: UIRect, orderInLayer: i32, renderer: &mut Renderer)
+    {
+        let mut style = self.Style;
+        style.Layer = ESortingLayer::UI;
+        style.OrderInLayer = orderInLayer;
+        style.MaxWidth = Some(rect.Width / PIXELS_PER_UNIT);
+
+        let (_, height) = self.Font.Measure(self.Text.as_str(), &style);
+        let height = height * PIXELS_PER_UNIT;
+
+        let x = match style.Align
+        {
+            ETextAlign::Left => rect.X,
+            ETextAlign::Center => rect.X + rect.Width * 0.5,
+            ETextAlign::Right => rect.X + rect.Width
+        };
+
+        let top = (rect.Y + (rect.Height + height) * 0.5).round();
+
+        renderer.DrawText(
+            &self.Font,
+            self.Text.as_str(),
+            Float3::new(x.round() / PIXELS_PER_UNIT, top / PIXELS_PER_UNIT, 0.0),
+            &style);
+    }
+}
+
+/// Largest rect with the texture's aspect ratio centered inside the given one.
+fn FitAspect(rect: UIRect, texture: &Texture2d) -> UIRect
+{
+    let aspect = texture.width() as f32 / texture.height().max(1) as f32;
+
+    let width = rect.Width.min(rect.Height * aspect);
+    let height = width / aspect;
+
+    UIRect::New(
+        (rect.X + (rect.Width - width) * 0.5).round(),
+        (rect.Y + (rect.Height - height) * 0.5).round(),
+        width.round(),
+        height.round())
+}
+
+fn MultiplyColor(a: [f32; 4], b: [f32; 4]) -> [f32; 4]
+{
+    [a[0] * b[0], a[1] * b[1], a[2] * b[2], a[3] * b[3]]
+}
+
+/// Submits one textured quad, the UI camera shows virtual pixels divided by PIXELS_PER_UNIT.
+fn SubmitRect(
+    renderer: &mut Renderer,
+    texture: &Arc<Texture2d>,
+    rect: UIRect,
+    uvMin: [f32; 2],
+    uvMax: [f32; 2],
+    color: [f32; 4],
+    orderInLayer: i32)
+{
+    let left = rect.X / PIXELS_PER_UNIT;
+    let right = (rect.X + rect.Width) / PIXELS_PER_UNIT;
+    let bottom = rect.Y / PIXELS_PER_UNIT;
+    let top = (rect.Y + rect.Height) / PIXELS_PER_UNIT;
+
+    renderer.Submit(
+        SpriteQuad
+        {
+            Texture: texture.clone(),
+            NormalMap: None,
+            Shader: String::from(DEFAULT_FRAGMENT),
+            Parameters: None,
+            Corners:
+            [
+                Float3::new(left, bottom, 0.0),
+                Float3::new(right, bottom, 0.0),
+                Float3::new(right, top, 0.0),
+                Float3::new(left, top, 0.0),
+            ],
+            UVMin: uvMin,
+            UVMax: uvMax,
+            Tint: color,
+            IsLit: false,
+            ScrollFactor: Float3::one(),
+            Layer: ESortingLayer::UI,
+            OrderInLayer: orderInLayer
+        });
+}
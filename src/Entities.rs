@@ -1,15 +1,22 @@
 use std::fmt::{Debug, Formatter};
 use std::sync::{Arc, Mutex};
-use glium::Frame;
 use uuid::Uuid;
 use crate::Boxer::Boxer;
 use crate::Engine::Components::AudioSource::AudioPlayer;
 use crate::Engine::Components::Camera::Camera;
+use crate::Engine::Components::light::Light;
+use crate::Engine::Components::parallax::ParallaxLayer;
+use crate::Engine::Components::particles::ParticleEffect;
+use crate::Engine::Components::text::Label;
 use crate::Engine::Frame::GameFrame;
 use crate::Engine::GameAPI::GameAPI;
+use crate::Engine::GameEntity::EntityHeader;
 use crate::Engine::GameEntity::TEntity;
 use crate::Engine::Math::Float3;
+use crate::Engine::Rendering::ESortingLayer;
+use crate::Engine::Rendering::virtual_screen::VirtualScreen;
 use crate::Engine::Tile::Tile;
+use crate::Engine::ui::UICanvas;
 
 
 #[derive(Debug)]
@@ -17,7 +24,11 @@ pub enum EEntityType
 {
     Boxer,
     Tiles,
-    AudioPlayer
+    AudioPlayer,
+    Light,
+    Parallax,
+    Particles,
+    Label
 }
 
 #[derive(Debug)]
@@ -25,16 +36,35 @@ pub enum EEntity
 {
     Boxer(Boxer),
     Tiles(Tile),
-    AudioPlayer(AudioPlayer)
+    AudioPlayer(AudioPlayer),
+    Light(Light),
+    Parallax(ParallaxLayer),
+    Particles(ParticleEffect),
+    Label(Label)
 }
 
 /// Collection of all entities that can exist in application
 pub struct Entities
 {
-    pub Camera : Camera,
+    /// Every camera in the scene. The first one is the main camera,
+    /// the second draws the UI layer in screen space.
+    pub Cameras : Vec<Camera>,
     pub Boxer: Vec<Boxer>,
     pub Tiles : Vec<Tile>,
     pub AudioSources: Vec<AudioPlayer>,
+    pub Lights: Vec<Light>,
+    pub ParallaxLayers: Vec<ParallaxLayer>,
+    pub ParticleEffects: Vec<ParticleEffect>,
+    pub Labels: Vec<Label>,
+
+    /// Light every lit sprite in the scene receives.
+    pub AmbientLight: Float3,
+
+    /// Layers sorted by height, see Renderer::SetYSort.
+    pub YSortedLayers: u32,
+
+    /// Menus and HUD drawn over the scene.
+    pub UI: UICanvas,
 
     _deadEntities: Vec<Uuid>,
 }
@@ -45,10 +75,17 @@ impl Entities
     {
         Self
         {
-            Camera: Camera::New(30.0, Float3::new(0.0, 1.0, 0.0)),
+            Cameras: vec![Self::MainCameraDefault(), Camera::NewScreenSpace("UI Camera", 100)],
             Boxer: Vec::new(),
             Tiles: Vec::new(),
             AudioSources: Vec::new(),
+            Lights: Vec::new(),
+            ParallaxLayers: Vec::new(),
+            ParticleEffects: Vec::new(),
+            Labels: Vec::new(),
+            AmbientLight: Float3::one(),
+            YSortedLayers: 0,
+            UI: UICanvas::Create(),
             _deadEntities: Vec::new()
         }
     }
@@ -74,41 +111,189 @@ impl Entities
             {
                 x.Start(a);
             }
+
+            for x in ent.Lights.iter_mut()
+            {
+                x.Start(a);
+            }
+
+            for x in ent.ParallaxLayers.iter_mut()
+            {
+                x.Start(a);
+            }
+
+            for x in ent.ParticleEffects.iter_mut()
+            {
+                x.Start(a);
+            }
+
+            for x in ent.Labels.iter_mut()
+            {
+                x.Start(a);
+            }
         }
     }
 
-    pub fn Update(frame: &GameFrame, api: &mut GameAPI, target: &mut Frame)
+    /// Updates every entity and submits it to the renderer.
+    pub fn Update(frame: &GameFrame, api: &mut GameAPI)
     {
 
         unsafe
         {
             let a: *mut GameAPI  = api;
             let mut ent = &mut api.SceneManager.Entities;
+            let renderer = &mut (*a).Renderer;
+
+
+            renderer.SetAmbientLight(ent.AmbientLight);
 
-            ent.Camera.Update(frame, a);
-            ent.Camera.Render(frame, target);
+            for layer in ESortingLayer::LAYERS
+            {
+                renderer.SetYSort(layer, ent.YSortedLayers & layer.Mask() != 0);
+            }
+
+            for x in ent.Lights.iter_mut()
+            {
+                x.Update(frame, a);
+                x.Render(frame, renderer);
+            }
 
 
             for x in ent.Tiles.iter_mut()
             {
                 x.Update(frame, a);
-                x.Render(frame, target);
+                x.Render(frame, renderer);
             }
 
             for x in ent.AudioSources.iter_mut()
             {
                 x.Update(frame, a);
-                x.Render(frame, target);
+                x.Render(frame, renderer);
             }
 
             for x in ent.Boxer.iter_mut()
             {
                 x.Update(frame, a);
-                x.Render(frame, target);
+                x.Render(frame, renderer);
+            }
+
+            for x in ent.ParticleEffects.iter_mut()
+            {
+                x.Update(frame, a);
+                x.Render(frame, renderer);
             }
+
+            for x in ent.Labels.iter_mut()
+            {
+                x.Update(frame, a);
+                x.Render(frame, renderer);
+            }
+
+            // Cameras go last so they see where their targets ended up this frame.
+            // What they read from the scene is gathered first, they never look through the camera list themselves.
+            let hovered =
+                if ent.UI.IsPointerOverUI()
+                {
+                    None
+                }
+                else
+                {
+                    ent.CameraAtScreenPoint(frame.Input.MousePosition(), &(*a).Screen).map(|x| x.Header.ID())
+                };
+
+            let targets: Vec<Option<Float3>> = ent.Cameras
+                .iter()
+                .map(|x| x.Follow.Target.and_then(|id| ent.FindHeader(&id)).map(|header| header.WorldPosition))
+                .collect();
+
+            for (x, target) in ent.Cameras.iter_mut().zip(targets)
+            {
+                x.PrepareUpdate(hovered == Some(x.Header.ID()), target);
+                x.Update(frame, a);
+                x.Render(frame, renderer);
+            }
+
+            // Parallax tiles cover every camera's view, so they wait for the cameras to move.
+            for x in ent.ParallaxLayers.iter_mut()
+            {
+                x.Update(frame, a);
+                x.Render(frame, renderer);
+            }
+
+            // Entities read the UI events raised last frame before they are cleared here.
+            ent.UI.Update(frame, &(*a).Screen);
+            ent.UI.Render(renderer);
         }
     }
 
+    /// Number of each kind of entity in the scene.
+    pub fn Counts(&self) -> Vec<(&'static str, usize)>
+    {
+        vec![
+            ("Cameras", self.Cameras.len()),
+            ("Boxers", self.Boxer.len()),
+            ("Tiles", self.Tiles.len()),
+            ("Audio Sources", self.AudioSources.len()),
+            ("Lights", self.Lights.len()),
+            ("Parallax Layers", self.ParallaxLayers.len()),
+            ("Particle Effects", self.ParticleEffects.len()),
+            ("Particles", self.ParticleEffects.iter().map(|x| x.Emitter.ParticleCount()).sum()),
+            ("Labels", self.Labels.len()),
+            ("Widgets", self.UI.WidgetCount())
+        ]
+    }
+
+    /// Main camera draws the world, the UI is left to the screen space camera.
+    fn MainCameraDefault() -> Camera
+    {
+        let mut camera = Camera::New(30.0, Float3::new(0.0, 1.0, 0.0));
+        camera.LayerMask = ESortingLayer::ALL & !ESortingLayer::UI.Mask();
+        camera
+    }
+
+    /// Camera gameplay code talks to, i.e. for following the player and shake.
+    pub fn MainCamera(&mut self) -> &mut Camera
+    {
+        &mut self.Cameras[0]
+    }
+
+    /// Adds a camera drawn alongside the main one, i.e. a minimap or a second player's view.
+    pub fn AddCamera(&mut self, camera: Camera)
+    {
+        self.Cameras.push(camera);
+    }
+
+    /// Topmost world camera drawn at a window position, i.e. the one the mouse is over.
+    /// Screen space cameras only draw the UI and are skipped.
+    pub fn CameraAtScreenPoint(&self, windowPosition: (f64, f64), screen: &VirtualScreen) -> Option<&Camera>
+    {
+        self.CamerasByPriority()
+            .into_iter()
+            .rev()
+            .find(|x| !x.IsScreenSpace && x.ContainsScreenPoint(windowPosition, screen))
+    }
+
+    /// Cameras in the order they should be drawn, lowest priority first.
+    pub fn CamerasByPriority(&self) -> Vec<&Camera>
+    {
+        let mut cameras: Vec<&Camera> = self.Cameras.iter().collect();
+        cameras.sort_by_key(|x| x.Priority);
+        cameras
+    }
+
+    /// Header of the entity with the given id, if it exists in the scene.
+    pub fn FindHeader(&self, id: &Uuid) -> Option<&EntityHeader>
+    {
+        self.Boxer.iter().map(|x| &x.Header)
+            .chain(self.Tiles.iter().map(|x| &x.Header))
+            .chain(self.AudioSources.iter().map(|x| &x.Header))
+            .chain(self.Lights.iter().map(|x| &x.Header))
+            .chain(self.ParallaxLayers.iter().map(|x| &x.Header))
+            .chain(self.ParticleEffects.iter().map(|x| &x.Header))
+            .chain(self.Labels.iter().map(|x| &x.Header))
+            .find(|x| x.ID() == *id)
+    }
+
     pub fn MarkEntityDead(&mut self, id: &Uuid)
     {
         if self._deadEntities.contains(id)
@@ -182,6 +367,10 @@ impl Entities
             EEntity::Boxer(e) => self.Boxer.push(e),
             EEntity::Tiles(e) => self.Tiles.push(e),
             EEntity::AudioPlayer(e) => self.AudioSources.push(e),
+            EEntity::Light(e) => self.Lights.push(e),
+            EEntity::Parallax(e) => self.ParallaxLayers.push(e),
+            EEntity::Particles(e) => self.ParticleEffects.push(e),
+            EEntity::Label(e) => self.Labels.push(e),
             e => !panic!("Unsupported entity type {:?}", e)
         }
     }
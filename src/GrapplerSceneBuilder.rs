@@ -1,201 +1,530 @@
-use glium::Display;
-use glium::glutin::surface::WindowSurface;
-
-use crate::Boxer;
-use crate::Engine::Audio::sample::{EAudioSpace, ETargetTrack};
-use crate::Engine::Collision::collider::{ECollisionTag, ECollisionType};
-use crate::Engine::Components::AudioSource::AudioPlayer;
-use crate::Engine::Components::Collider;
-use crate::Engine::Components::RenderComponents::Sprite;
-use crate::Engine::Math::Float3;
-use crate::Engine::SceneBuilder::TSceneBuilder;
-use crate::Engine::Tile::Tile;
-use crate::Entities::Entities;
-
-pub struct GCSBSceneBuilder
-{
-}
-
-impl TSceneBuilder for GCSBSceneBuilder
-{
-    fn LoadScene(
-        name: String,
-        rawScene: String,
-        display: &Display<WindowSurface>)
-        -> Entities
-    {
-        println!("Loaded Scene: {}", name);
-
-        let lines = rawScene.lines().filter( |x| !x.contains("*"));
-
-        let mut entities = Entities::Create();
-
-        lines.for_each( |x| Self::ParseEntity(x, &mut entities, display));
-
-        entities
-    }
-}
-
-impl GCSBSceneBuilder
-{
-    pub fn create() -> Self
-    {
-        Self{}
-    }
-
-    fn ParseEntity(entry: &str, entities: &mut Entities, display: &Display<WindowSurface>)
-    {
-        let mut tokens : Vec<String> = Vec::new();
-        entry
-            .split(crate::Engine::SceneBuilder::PROPERTY_SEPARATOR)
-            .for_each(|x| tokens.push(String::from(x)));
-
-        let objectType = &tokens.first().unwrap();
-
-        match objectType.as_str()
-        {
-            "Player" => Self::BuildPlayer(tokens, entities, display),
-            "Tile" => Self::BuildTile(tokens, entities, display),
-            "Audio" => Self::BuildAudioSource(tokens, entities, display),
-            _ => {}
-        };
-    }
-
-    /// constructs a player object
-    ///
-    /// Entry Structure:
-    ///     1 - name
-    ///     2 - position
-    fn BuildPlayer(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>)
-    {
-        println!("Player");
-        let name = data[1].as_str();
-        let position = Float3::FromString(data[2].as_str());
-
-        let mut isLit = false;
-        
-        if data.len() >= 4
-        {
-            isLit = data[3].as_str().parse().unwrap();
-        }
-
-        let player = Boxer::Boxer::Create(
-            32.0,
-            display,
-            position);
-
-        entities.Boxer.push(player);
-    }
-
-    /// Builds a static tile object
-    ///
-    /// Entry Structure:
-    ///     1 - name
-    ///     2 - position
-    ///     3 - asset path
-    ///     4 - is a collider
-    ///     5 - tag
-    fn BuildTile(data: Vec<String>, entities: &mut Entities,  display: &Display<WindowSurface>)
-    {
-        println!("Tile");
-        // 1 - name
-        let name = data[1].as_str();
-
-        // 2 - position
-        let position = Float3::FromString(data[2].as_str());
-
-        // 3 - asset path
-        let assetPath = data[3].as_str();
-
-        // 4 - is a collider - TODO
-        let mut hasCollider = false;
-
-        if data.len() >= 5
-        {
-            let o: Result<bool, _> = data[4].as_str().parse();
-
-            if(o.is_ok())
-            {
-                hasCollider = data[4].as_str().parse().unwrap();
-
-            }
-        }
-
-        // 5 - is lit
-        let mut isLit = false;
-        if data.len() >= 6
-        {
-            isLit = data[5].as_str().parse().unwrap();
-        }
-
-        let mut collider = None;
-        if hasCollider
-        {
-            collider = Some(
-                Collider::Collider::Create(
-                    display.clone(),
-                    position,
-                    Float3::new(32.0, 32.0, 1.0),
-                    ECollisionType::Solid,
-                    ECollisionTag::None
-                )
-            );
-        }
-        else
-        {
-            collider = None;
-        }
-
-        let tile =
-            Tile::Create(
-                name,
-                position,
-                Sprite::new(
-                    assetPath,
-                    &display,
-                    1,
-                    (1,1),
-                    0.001),
-                isLit,
-                display,
-                collider
-            );
-
-        entities.Tiles.push(tile);
-    }
-
-    /// Constructs an audio source object.
-    ///
-    /// Entry Structure:
-    ///     1 - name
-    ///     2 - position
-    ///     3 - path
-    ///     4 - volume
-    ///     5 - space(2D/ 3D)
-    ///     6 - track(music/ sfx)
-    ///
-    fn BuildAudioSource(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>)
-    {
-        println!("Audio");
-        let name = data[1].as_str();
-        let position = Float3::FromString(data[2].as_str());
-
-        let assetPath = String::from(data[3].as_str());
-
-        let volume : f32 = data[4].as_str().trim().parse().unwrap();
-
-        // TODO: implement remaining properties
-        let audioSource =
-            AudioPlayer::Create(
-                name,
-                position,
-                assetPath,
-                volume,
-                true,
-                EAudioSpace::Is2D,
-                ETargetTrack::Music
-            );
-
-        entities.AudioSources.push(audioSource);
-    }
-}
+use glium::Display;
+use glium::glutin::surface::WindowSurface;
+
+use crate::Boxer;
+use crate::Engine::assets::AssetManager;
+use crate::Engine::Audio::sample::{EAudioSpace, ETargetTrack};
+use crate::Engine::Collision::collider::{ECollisionTag, ECollisionType};
+use crate::Engine::Components::AudioSource::AudioPlayer;
+use crate::Engine::Components::Camera::{Camera, EProjectionType, ViewportRect};
+use crate::Engine::Components::Collider;
+use crate::Engine::Components::light::Light;
+use crate::Engine::Components::parallax::ParallaxLayer;
+use crate::Engine::Components::particles::{ParticleEffect, ParticleEmitter};
+use crate::Engine::Components::text::{Label, TextRenderer};
+use crate::Engine::Components::RenderComponents::LightSource;
+use crate::Engine::Math::Float3;
+use crate::Engine::Rendering::ESortingLayer;
+use crate::Engine::Rendering::text::{ETextAlign, TextStyle};
+use crate::Engine::SceneBuilder::TSceneBuilder;
+use crate::Engine::Tile::Tile;
+use crate::Entities::Entities;
+
+pub struct GCSBSceneBuilder
+{
+}
+
+impl TSceneBuilder for GCSBSceneBuilder
+{
+    fn LoadScene(
+        name: String,
+        rawScene: String,
+        display: &Display<WindowSurface>,
+        assets: &mut AssetManager)
+        -> Entities
+    {
+        println!("Loaded Scene: {}", name);
+
+        let lines = rawScene.lines().filter( |x| !x.contains("*"));
+
+        let mut entities = Entities::Create();
+
+        lines.for_each( |x| Self::ParseEntity(x, &mut entities, display, assets));
+
+        entities
+    }
+}
+
+impl GCSBSceneBuilder
+{
+    pub fn create() -> Self
+    {
+        Self{}
+    }
+
+    fn ParseEntity(entry: &str, entities: &mut Entities, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        let mut tokens : Vec<String> = Vec::new();
+        entry
+            .split(crate::Engine::SceneBuilder::PROPERTY_SEPARATOR)
+            .for_each(|x| tokens.push(String::from(x)));
+
+        let objectType = &tokens.first().unwrap();
+
+        match objectType.as_str()
+        {
+            "Player" => Self::BuildPlayer(tokens, entities, display, assets),
+            "Tile" => Self::BuildTile(tokens, entities, display, assets),
+            "Audio" => Self::BuildAudioSource(tokens, entities, display),
+            "Light" => Self::BuildLight(tokens, entities),
+            "Parallax" => Self::BuildParallax(tokens, entities, display, assets),
+            "Particles" => Self::BuildParticles(tokens, entities, display, assets),
+            "Text" => Self::BuildText(tokens, entities, display, assets),
+            "Camera" => Self::BuildCamera(tokens, entities),
+            "View" => Self::BuildView(tokens, entities),
+            "UI" => entities.UI.Load(tokens[1].trim(), display, assets),
+            "Ambient" => entities.AmbientLight = Float3::FromString(tokens[1].as_str()),
+            "YSort" => entities.YSortedLayers |= ESortingLayer::MaskFromString(tokens[1].as_str()),
+            _ => {}
+        };
+    }
+
+    /// constructs a player object
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position
+    fn BuildPlayer(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        println!("Player");
+        let name = data[1].as_str();
+        let position = Float3::FromString(data[2].as_str());
+
+        let mut isLit = false;
+        
+        if data.len() >= 4
+        {
+            isLit = data[3].as_str().parse().unwrap();
+        }
+
+        let player = Boxer::Boxer::Create(
+            32.0,
+            display,
+            position,
+            assets);
+
+        // Camera follows the player unless the scene says otherwise.
+        entities.MainCamera().Follow.SetTarget(Some(player.Header.ID()));
+        entities.Boxer.push(player);
+    }
+
+    /// Builds a static tile object
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position
+    ///     3 - asset path
+    ///     4 - is a collider
+    ///     5 - is lit
+    ///     6 - sorting layer (Background/World/Characters/Foreground/UI)
+    ///     7 - order in layer
+    ///     8 - normal map path
+    ///     9 - material path
+    ///     10+ - material parameter overrides, name=value
+    fn BuildTile(data: Vec<String>, entities: &mut Entities,  display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        println!("Tile");
+        // 1 - name
+        let name = data[1].as_str();
+
+        // 2 - position
+        let position = Float3::FromString(data[2].as_str());
+
+        // 3 - asset path
+        let assetPath = data[3].as_str();
+
+        // 4 - is a collider - TODO
+        let mut hasCollider = false;
+
+        if data.len() >= 5
+        {
+            let o: Result<bool, _> = data[4].as_str().parse();
+
+            if(o.is_ok())
+            {
+                hasCollider = data[4].as_str().parse().unwrap();
+
+            }
+        }
+
+        // 5 - is lit
+        let mut isLit = false;
+        if data.len() >= 6
+        {
+            isLit = data[5].as_str().parse().unwrap();
+        }
+
+        // 8 - normal map path
+        let normalMapPath = data
+            .get(8)
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty());
+
+        // Animated gifs carry their own frame layout and timing.
+        let sprite =
+            if let Some(normalMapPath) = normalMapPath
+            {
+                assets.NormalMappedSprite(
+                    assetPath,
+                    normalMapPath,
+                    &display,
+                    1,
+                    (1,1),
+                    0.001)
+            }
+            else if assetPath.trim().ends_with(".gif")
+            {
+                assets.GifSprite(assetPath, &display)
+            }
+            else
+            {
+                assets.Sprite(
+                    assetPath,
+                    &display,
+                    1,
+                    (1,1),
+                    0.001)
+            };
+
+        // Tiles collide with the bounds of one sprite cell, in pixels.
+        let collider =
+            if hasCollider
+            {
+                let (width, height) = sprite.Texture.dimensions();
+
+                Some(
+                    Collider::Collider::Create(
+                        position,
+                        Float3::new(
+                            width as f32 / sprite.CellCounts.0 as f32,
+                            height as f32 / sprite.CellCounts.1 as f32,
+                            1.0),
+                        ECollisionType::Solid,
+                        ECollisionTag::None
+                    )
+                )
+            }
+            else
+            {
+                None
+            };
+
+        // 6 - sorting layer
+        let mut layer = ESortingLayer::World;
+        if data.len() >= 7
+        {
+            layer = ESortingLayer::FromString(data[6].as_str());
+        }
+
+        // 7 - order in layer
+        let mut orderInLayer = 0;
+        if data.len() >= 8
+        {
+            orderInLayer = data[7].as_str().trim().parse().unwrap();
+        }
+
+        let mut tile =
+            Tile::Create(
+                name,
+                position,
+                sprite,
+                isLit,
+                collider
+            );
+
+        tile.SetSortingLayer(layer, orderInLayer);
+
+        // 9 - material path
+        if let Some(materialPath) = data.get(9).map(|x| x.trim()).filter(|x| !x.is_empty())
+        {
+            let material = assets.Material(materialPath, display);
+            tile.Renderer().SetMaterial(&material);
+        }
+
+        // 10+ - material parameter overrides
+        for entry in data.iter().skip(10).map(|x| x.trim()).filter(|x| !x.is_empty())
+        {
+            tile.Renderer().MaterialMut().SetFromString(entry, display, assets);
+        }
+
+        entities.Tiles.push(tile);
+    }
+
+    /// Builds a layer that scrolls relative to the main camera.
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position
+    ///     3 - asset path
+    ///     4 - scroll factor (x, y, 0), 1 moves with the world, 0 stays on screen
+    ///     5 - tiling (None/X/Y/XY)
+    ///     6 - auto scroll (x, y, 0) in units per second (optional)
+    ///     7 - sorting layer (optional, Background by default)
+    ///     8 - order in layer (optional)
+    ///     9 - is lit (optional)
+    fn BuildParallax(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        println!("Parallax");
+        let name = data[1].as_str();
+        let position = Float3::FromString(data[2].as_str());
+        let texture = assets.Texture(data[3].as_str().trim(), display);
+        let scrollFactor = Float3::FromString(data[4].as_str());
+
+        let mut layer = ParallaxLayer::Create(name, position, texture, scrollFactor);
+
+        let tiling = data[5].trim().to_uppercase();
+        match tiling.as_str()
+        {
+            "NONE" | "X" | "Y" | "XY" => layer.SetTiling(tiling.contains('X'), tiling.contains('Y')),
+            x => panic!("Parallax {}: unknown tiling {}", name, x)
+        }
+
+        if let Some(x) = data.get(6)
+        {
+            layer.AutoScroll = Float3::FromString(x.as_str());
+        }
+
+        let sortingLayer = match data.get(7)
+        {
+            Some(x) => ESortingLayer::FromString(x.as_str()),
+            None => ESortingLayer::Background
+        };
+
+        let orderInLayer = match data.get(8)
+        {
+            Some(x) => x.trim().parse().unwrap(),
+            None => 0
+        };
+
+        layer.SetSortingLayer(sortingLayer, orderInLayer);
+
+        if let Some(x) = data.get(9)
+        {
+            layer.IsLit = x.trim().parse().unwrap();
+        }
+
+        entities.ParallaxLayers.push(layer);
+    }
+
+    /// Places a particle emitter loaded from a preset file.
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position
+    ///     3 - preset path
+    fn BuildParticles(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        println!("Particles");
+        let name = data[1].as_str();
+        let position = Float3::FromString(data[2].as_str());
+        let emitter = ParticleEmitter::FromFile(data[3].as_str().trim(), display, assets);
+
+        entities.ParticleEffects.push(ParticleEffect::Create(name, position, emitter));
+    }
+
+    /// Places a line of text. On the UI layer the position is in screen pixels
+    /// from the bottom left, otherwise it is a world position.
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position, top of the text
+    ///     3 - font path (.fnt or .ttf)
+    ///     4 - pixel size, ignored by bitmap fonts
+    ///     5 - text
+    ///     6 - color
+    ///     7 - alignment (Left/Center/Right)
+    ///     8 - sorting layer
+    ///     9 - scale (optional)
+    fn BuildText(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>, assets: &mut AssetManager)
+    {
+        println!("Text");
+        let name = data[1].as_str();
+        let position = Float3::FromString(data[2].as_str());
+        let pixelSize: f32 = data[4].trim().parse().unwrap();
+        let font = assets.Font(data[3].as_str(), pixelSize, false, display);
+        let color = Float3::FromString(data[6].as_str());
+
+        let mut style = TextStyle::Create();
+        style.Color = [color.x(), color.y(), color.z(), 1.0];
+        style.Align = match data[7].trim().to_lowercase().as_str()
+        {
+            "left" => ETextAlign::Left,
+            "center" => ETextAlign::Center,
+            "right" => ETextAlign::Right,
+            x => panic!("Text {}: unknown alignment {}", name, x)
+        };
+        style.Layer = ESortingLayer::FromString(data[8].as_str());
+
+        if let Some(x) = data.get(9)
+        {
+            style.Scale = x.trim().parse().unwrap();
+        }
+
+        let text = TextRenderer::New(font, data[5].trim(), style);
+        entities.Labels.push(Label::Create(name, position, text));
+    }
+
+    /// Configures how the main camera follows its target.
+    ///
+    /// Entry Structure:
+    ///     1 - damping
+    ///     2 - deadzone (width, height, 0)
+    ///     3 - look ahead (x, y, 0)
+    ///     4 - bounds min (optional)
+    ///     5 - bounds max (optional)
+    fn BuildCamera(data: Vec<String>, entities: &mut Entities)
+    {
+        println!("Camera");
+        let follow = &mut entities.MainCamera().Follow;
+
+        follow.Damping = data[1].as_str().trim().parse().unwrap();
+        follow.Deadzone = Float3::FromString(data[2].as_str());
+        follow.LookAhead = Float3::FromString(data[3].as_str());
+
+        if data.len() >= 6
+        {
+            follow.Bounds = Some(
+                (
+                    Float3::FromString(data[4].as_str()),
+                    Float3::FromString(data[5].as_str())
+                ));
+        }
+    }
+
+    /// Adds a camera drawn alongside the main one, i.e. a minimap.
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - viewport (x, y, width, height) as fractions of the screen
+    ///     3 - sorting layers, comma separated or All
+    ///     4 - clear color, or None to draw over the cameras below
+    ///     5 - priority
+    ///     6 - zoom
+    ///     7 - position (optional)
+    ///     8 - projection, Orthographic or Perspective (optional)
+    ///     9 - field of view in degrees for perspective (optional)
+    fn BuildView(data: Vec<String>, entities: &mut Entities)
+    {
+        println!("View");
+        let rect: Vec<f32> = data[2]
+            .split(',')
+            .map(|x| x.trim().parse().unwrap())
+            .collect();
+
+        if rect.len() != 4
+        {
+            panic!("View {} needs a viewport of x, y, width, height", data[1].trim());
+        }
+
+        let position = match data.get(7)
+        {
+            Some(x) => Float3::FromString(x.as_str()),
+            None => Float3::zero()
+        };
+
+        let mut camera = Camera::New(30.0, position);
+        camera.Header.Name = String::from(data[1].trim());
+        camera.Viewport = ViewportRect::New(rect[0], rect[1], rect[2], rect[3]);
+        camera.LayerMask = ESortingLayer::MaskFromString(data[3].as_str());
+        camera.ClearColor =
+            if data[4].trim().eq_ignore_ascii_case("none")
+            {
+                None
+            }
+            else
+            {
+                let c = Float3::FromString(data[4].as_str());
+                Some((c.x(), c.y(), c.z(), 1.0))
+            };
+        camera.Priority = data[5].trim().parse().unwrap();
+        camera.Zoom = data[6].trim().parse().unwrap();
+
+        if let Some(x) = data.get(8)
+        {
+            camera.Projection = EProjectionType::FromString(x.as_str());
+        }
+
+        if let Some(x) = data.get(9)
+        {
+            camera.FieldOfView = x.trim().parse().unwrap();
+        }
+
+        entities.AddCamera(camera);
+    }
+
+    /// Constructs a point or spot light.
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position
+    ///     3 - color
+    ///     4 - intensity
+    ///     5 - radius
+    ///     6 - falloff
+    ///     7 - spot direction (optional)
+    ///     8 - spot cone angle in degrees (optional)
+    fn BuildLight(data: Vec<String>, entities: &mut Entities)
+    {
+        println!("Light");
+        let name = data[1].as_str();
+        let position = Float3::FromString(data[2].as_str());
+        let color = Float3::FromString(data[3].as_str());
+
+        let intensity: f32 = data[4].as_str().trim().parse().unwrap();
+        let radius: f32 = data[5].as_str().trim().parse().unwrap();
+        let falloff: f32 = data[6].as_str().trim().parse().unwrap();
+
+        let source =
+            if data.len() >= 9
+            {
+                LightSource::Spot(
+                    color,
+                    intensity,
+                    radius,
+                    falloff,
+                    Float3::FromString(data[7].as_str()),
+                    data[8].as_str().trim().parse().unwrap())
+            }
+            else
+            {
+                LightSource::Point(color, intensity, radius, falloff)
+            };
+
+        entities.Lights.push(Light::Create(name, position, source));
+    }
+
+    /// Constructs an audio source object.
+    ///
+    /// Entry Structure:
+    ///     1 - name
+    ///     2 - position
+    ///     3 - path
+    ///     4 - volume
+    ///     5 - space(2D/ 3D)
+    ///     6 - track(music/ sfx)
+    ///
+    fn BuildAudioSource(data: Vec<String>, entities: &mut Entities, display: &Display<WindowSurface>)
+    {
+        println!("Audio");
+        let name = data[1].as_str();
+        let position = Float3::FromString(data[2].as_str());
+
+        let assetPath = String::from(data[3].as_str());
+
+        let volume : f32 = data[4].as_str().trim().parse().unwrap();
+
+        // TODO: implement remaining properties
+        let audioSource =
+            AudioPlayer::Create(
+                name,
+                position,
+                assetPath,
+                volume,
+                true,
+                EAudioSpace::Is2D,
+                ETargetTrack::Music
+            );
+
+        entities.AudioSources.push(audioSource);
+    }
+}